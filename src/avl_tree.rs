@@ -1,16 +1,43 @@
 use std::{
     cell::RefCell,
     cmp::{Ord, Ordering},
+    fmt,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
     rc::Rc,
 };
 
-type Link<K, V> = Option<Rc<RefCell<Node<K, V>>>>;
+type NodeRef<K, V> = Rc<RefCell<Node<K, V>>>;
+type Link<K, V> = Option<NodeRef<K, V>>;
 
-pub enum AVLTreeError {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AVLTreeError<K, V> {
     EmptyAVLTree,
     MaxNodesExists,
     NodeNotFound,
-    AlreadyExists,
+    AlreadyExists { key: K, value: V },
+}
+
+impl<K, V> fmt::Display for AVLTreeError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AVLTreeError::EmptyAVLTree => write!(f, "the tree is empty"),
+            AVLTreeError::MaxNodesExists => {
+                write!(f, "the tree already holds the maximum number of nodes")
+            }
+            AVLTreeError::NodeNotFound => write!(f, "no node with the given key exists"),
+            AVLTreeError::AlreadyExists { .. } => {
+                write!(f, "a node with the given key already exists")
+            }
+        }
+    }
+}
+
+impl<K, V> std::error::Error for AVLTreeError<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
 }
 
 struct Node<K, V>
@@ -19,6 +46,7 @@ where
 {
     pub key: K,
     pub value: V,
+    pub height: usize,
     pub left: Link<K, V>,
     pub right: Link<K, V>,
 }
@@ -31,13 +59,14 @@ where
         Node {
             key,
             value,
+            height: 1,
             left: None,
             right: None,
         }
     }
 }
 
-struct AVLTree<K, V>
+pub struct AVLTree<K, V>
 where
     K: PartialEq + Ord,
 {
@@ -45,6 +74,15 @@ where
     size: usize,
 }
 
+impl<K, V> Default for AVLTree<K, V>
+where
+    K: PartialEq + Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K, V> AVLTree<K, V>
 where
     K: PartialEq + Ord,
@@ -56,7 +94,24 @@ where
         }
     }
 
-    pub fn insert(&mut self, key: K, value: V) -> Result<(), AVLTreeError> {
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn height(&self) -> usize {
+        Self::height_of(&self.head)
+    }
+
+    pub fn clear(&mut self) {
+        self.head = None;
+        self.size = 0;
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Result<(), AVLTreeError<K, V>> {
         if self.size == usize::MAX {
             return Err(AVLTreeError::MaxNodesExists);
         }
@@ -67,79 +122,484 @@ where
             return Ok(());
         }
 
-        self.insert_val(key, value, self.head.clone())
+        let head = self.head.clone().unwrap();
+        self.head = Some(Self::insert_val(key, value, &head)?);
+        self.size += 1;
+
+        Ok(())
     }
 
-    pub fn get(&self, key: K) -> Result<V, AVLTreeError> {
+    pub fn replace(&mut self, key: K, value: V) -> Result<V, AVLTreeError<K, V>> {
         if self.size == 0 {
             return Err(AVLTreeError::EmptyAVLTree);
         }
-        let node = self.head.clone();
 
-        return self.get_val(key, node);
+        match self.find(&key) {
+            Some(node) => Ok(std::mem::replace(&mut node.borrow_mut().value, value)),
+            None => Err(AVLTreeError::NodeNotFound),
+        }
     }
 
-    #[inline]
-    fn insert_val(&mut self, key: K, value: V, node: Link<K, V>) -> Result<(), AVLTreeError> {
-        let cur_node = node.unwrap();
-        let cur_key = &cur_node.borrow().key;
+    pub fn get(&self, key: K) -> Result<V, AVLTreeError<K, V>>
+    where
+        V: Clone,
+    {
+        if self.size == 0 {
+            return Err(AVLTreeError::EmptyAVLTree);
+        }
 
-        return match key.cmp(cur_key) {
-            Ordering::Equal => return Err(AVLTreeError::AlreadyExists),
-            Ordering::Less => {
-                let mut node = (*cur_node).borrow_mut();
-                let left = (*node).left.clone();
-                if left.is_some() {
-                    return self.insert_val(key, value, left);
-                }
+        match self.find(&key) {
+            Some(node) => Ok(node.borrow().value.clone()),
+            None => Err(AVLTreeError::NodeNotFound),
+        }
+    }
 
-                (*node).left = Some(Rc::new(RefCell::new(Node::new(key, value))));
-                self.size += 1;
-                Ok(())
-            }
-            Ordering::Greater => {
-                let mut node = (*cur_node).borrow_mut();
-                let right = (*node).right.clone();
-                if right.is_some() {
-                    return self.insert_val(key, value, right);
-                }
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
 
-                (*node).right = Some(Rc::new(RefCell::new(Node::new(key, value))));
-                self.size += 1;
-                Ok(())
-            }
+    pub fn remove(&mut self, key: K) -> Result<V, AVLTreeError<K, V>> {
+        if self.size == 0 {
+            return Err(AVLTreeError::EmptyAVLTree);
+        }
+
+        if !self.contains_key(&key) {
+            return Err(AVLTreeError::NodeNotFound);
+        }
+
+        let head = self.head.take().unwrap();
+        let (head, removed) = Self::remove_val(&key, head);
+        self.head = head;
+        self.size -= 1;
+
+        let Ok(removed) = Rc::try_unwrap(removed) else {
+            unreachable!("a removed node is only owned by the tree");
         };
+
+        Ok(removed.into_inner().value)
     }
 
-    fn get_val(&self, key: K, node: Link<K, V>) -> Result<V, AVLTreeError> {
-        if node.is_none() {
-            return Err(AVLTreeError::NodeNotFound);
+    pub fn first(&self) -> Result<(K, V), AVLTreeError<K, V>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.edge(|node| node.left.clone())
+    }
+
+    pub fn last(&self) -> Result<(K, V), AVLTreeError<K, V>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.edge(|node| node.right.clone())
+    }
+
+    pub fn iter(&self) -> Range<'_, K, V>
+    where
+        K: Clone,
+    {
+        self.range(..)
+    }
+
+    pub fn range<R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Clone,
+        R: RangeBounds<K>,
+    {
+        let mut stack = Vec::new();
+        let mut node = self.head.clone();
+
+        while let Some(current) = node {
+            let above_start = match range.start_bound() {
+                Bound::Included(start) => current.borrow().key >= *start,
+                Bound::Excluded(start) => current.borrow().key > *start,
+                Bound::Unbounded => true,
+            };
+
+            if above_start {
+                node = current.borrow().left.clone();
+                stack.push(current);
+            } else {
+                node = current.borrow().right.clone();
+            }
         }
-        let node = node.unwrap();
+
+        Range {
+            stack,
+            end: range.end_bound().cloned(),
+            marker: PhantomData,
+        }
+    }
+
+    fn find(&self, key: &K) -> Link<K, V> {
+        let mut node = self.head.clone();
+
+        while let Some(current) = node {
+            let ordering = key.cmp(&current.borrow().key);
+            node = match ordering {
+                Ordering::Equal => return Some(current),
+                Ordering::Less => current.borrow().left.clone(),
+                Ordering::Greater => current.borrow().right.clone(),
+            };
+        }
+
+        None
+    }
+
+    fn edge<F>(&self, next: F) -> Result<(K, V), AVLTreeError<K, V>>
+    where
+        K: Clone,
+        V: Clone,
+        F: Fn(&Node<K, V>) -> Link<K, V>,
+    {
+        let Some(mut node) = self.head.clone() else {
+            return Err(AVLTreeError::EmptyAVLTree);
+        };
+
+        loop {
+            let child = next(&node.borrow());
+            match child {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+
         let node = node.borrow();
-        let cur_key = &node.key;
+        Ok((node.key.clone(), node.value.clone()))
+    }
+
+    // Returns the new root of the subtree so the caller can relink it, since
+    // rotations may have moved a different node to the top.
+    fn insert_val(
+        key: K,
+        value: V,
+        node: &NodeRef<K, V>,
+    ) -> Result<NodeRef<K, V>, AVLTreeError<K, V>> {
+        let ordering = key.cmp(&node.borrow().key);
+
+        match ordering {
+            Ordering::Equal => return Err(AVLTreeError::AlreadyExists { key, value }),
+            Ordering::Less => {
+                let left = node.borrow().left.clone();
+                let left = match left {
+                    Some(left) => Self::insert_val(key, value, &left)?,
+                    None => Rc::new(RefCell::new(Node::new(key, value))),
+                };
+                node.borrow_mut().left = Some(left);
+            }
+            Ordering::Greater => {
+                let right = node.borrow().right.clone();
+                let right = match right {
+                    Some(right) => Self::insert_val(key, value, &right)?,
+                    None => Rc::new(RefCell::new(Node::new(key, value))),
+                };
+                node.borrow_mut().right = Some(right);
+            }
+        }
 
-        return match key.cmp(cur_key) {
-            Ordering::Equal => return Ok(node.value),
+        Ok(Self::rebalance(node.clone()))
+    }
+
+    // The key must be present. Returns the new subtree root and the detached
+    // node that held the key.
+    fn remove_val(key: &K, node: NodeRef<K, V>) -> (Link<K, V>, NodeRef<K, V>) {
+        let ordering = key.cmp(&node.borrow().key);
+
+        match ordering {
             Ordering::Less => {
-                let left = node.left.clone();
-                self.get_val(key, left)
+                let left = node.borrow_mut().left.take().unwrap();
+                let (left, removed) = Self::remove_val(key, left);
+                node.borrow_mut().left = left;
+                (Some(Self::rebalance(node)), removed)
             }
             Ordering::Greater => {
-                let right = node.right.clone();
-                self.get_val(key, right)
+                let right = node.borrow_mut().right.take().unwrap();
+                let (right, removed) = Self::remove_val(key, right);
+                node.borrow_mut().right = right;
+                (Some(Self::rebalance(node)), removed)
+            }
+            Ordering::Equal => {
+                let (left, right) = {
+                    let mut node = node.borrow_mut();
+                    (node.left.take(), node.right.take())
+                };
+
+                match (left, right) {
+                    (None, None) => (None, node),
+                    (Some(child), None) | (None, Some(child)) => (Some(child), node),
+                    (Some(left), Some(right)) => {
+                        let (right, successor) = Self::remove_min(right);
+                        {
+                            let mut successor = successor.borrow_mut();
+                            successor.left = Some(left);
+                            successor.right = right;
+                        }
+                        (Some(Self::rebalance(successor)), node)
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove_min(node: NodeRef<K, V>) -> (Link<K, V>, NodeRef<K, V>) {
+        let left = node.borrow_mut().left.take();
+
+        match left {
+            Some(left) => {
+                let (left, min) = Self::remove_min(left);
+                node.borrow_mut().left = left;
+                (Some(Self::rebalance(node)), min)
             }
+            None => {
+                let right = node.borrow_mut().right.take();
+                (right, node)
+            }
+        }
+    }
+
+    fn height_of(link: &Link<K, V>) -> usize {
+        link.as_ref().map_or(0, |node| node.borrow().height)
+    }
+
+    fn update_height(node: &NodeRef<K, V>) {
+        let mut node = node.borrow_mut();
+        node.height = 1 + Self::height_of(&node.left).max(Self::height_of(&node.right));
+    }
+
+    fn balance_factor(node: &NodeRef<K, V>) -> isize {
+        let node = node.borrow();
+        Self::height_of(&node.left) as isize - Self::height_of(&node.right) as isize
+    }
+
+    fn rotate_left(node: NodeRef<K, V>) -> NodeRef<K, V> {
+        let right = node.borrow_mut().right.take().unwrap();
+        node.borrow_mut().right = right.borrow_mut().left.take();
+        Self::update_height(&node);
+
+        right.borrow_mut().left = Some(node);
+        Self::update_height(&right);
+        right
+    }
+
+    fn rotate_right(node: NodeRef<K, V>) -> NodeRef<K, V> {
+        let left = node.borrow_mut().left.take().unwrap();
+        node.borrow_mut().left = left.borrow_mut().right.take();
+        Self::update_height(&node);
+
+        left.borrow_mut().right = Some(node);
+        Self::update_height(&left);
+        left
+    }
+
+    fn rebalance(node: NodeRef<K, V>) -> NodeRef<K, V> {
+        Self::update_height(&node);
+        let balance = Self::balance_factor(&node);
+
+        if balance > 1 {
+            let left = node.borrow_mut().left.take().unwrap();
+            let left = if Self::balance_factor(&left) < 0 {
+                Self::rotate_left(left)
+            } else {
+                left
+            };
+            node.borrow_mut().left = Some(left);
+            return Self::rotate_right(node);
+        }
+
+        if balance < -1 {
+            let right = node.borrow_mut().right.take().unwrap();
+            let right = if Self::balance_factor(&right) > 0 {
+                Self::rotate_right(right)
+            } else {
+                right
+            };
+            node.borrow_mut().right = Some(right);
+            return Self::rotate_left(node);
+        }
+
+        node
+    }
+}
+
+pub struct Range<'a, K, V>
+where
+    K: PartialEq + Ord,
+{
+    stack: Vec<NodeRef<K, V>>,
+    end: Bound<K>,
+    marker: PhantomData<&'a AVLTree<K, V>>,
+}
+
+impl<K, V> Iterator for Range<'_, K, V>
+where
+    K: PartialEq + Ord + Clone,
+    V: Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        let node = self.stack.pop()?;
+        let node = node.borrow();
+
+        let below_end = match &self.end {
+            Bound::Included(end) => node.key <= *end,
+            Bound::Excluded(end) => node.key < *end,
+            Bound::Unbounded => true,
         };
+        if !below_end {
+            self.stack.clear();
+            return None;
+        }
+
+        let mut child = node.right.clone();
+        while let Some(current) = child {
+            child = current.borrow().left.clone();
+            self.stack.push(current);
+        }
+
+        Some((node.key.clone(), node.value.clone()))
     }
 }
 
 #[cfg(test)]
 mod avl_tree_tests {
-    use super::AVLTree;
+    use super::{AVLTree, AVLTreeError};
 
     #[test]
     fn test_get() {
         let mut tree = AVLTree::new();
-        tree.insert(5, String::from("Hello World"));
+        tree.insert(5, String::from("Hello World")).unwrap();
+        tree.insert(2, String::from("Two")).unwrap();
+        tree.insert(8, String::from("Eight")).unwrap();
+
+        assert_eq!(tree.get(5), Ok(String::from("Hello World")));
+        assert_eq!(tree.get(2), Ok(String::from("Two")));
+        assert_eq!(tree.get(8), Ok(String::from("Eight")));
+        assert_eq!(tree.size(), 3);
+    }
+
+    #[test]
+    fn test_balanced() {
+        let mut tree = AVLTree::new();
+        for key in 0..1024 {
+            tree.insert(key, key * 2).unwrap();
+        }
+
+        assert_eq!(tree.size(), 1024);
+        assert_eq!(tree.height(), 11);
+        for key in 0..1024 {
+            assert_eq!(tree.get(key), Ok(key * 2));
+        }
+
+        for key in (0..1024).rev() {
+            assert_eq!(tree.remove(key), Ok(key * 2));
+            let size = tree.size() as f64;
+            assert!(tree.height() as f64 <= 1.45 * (size + 2.0).log2());
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.height(), 0);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut tree = AVLTree::new();
+        for key in [50, 30, 70, 20, 40, 60, 80, 35, 45, 65] {
+            tree.insert(key, key).unwrap();
+        }
+
+        assert_eq!(tree.remove(30), Ok(30));
+        assert_eq!(tree.remove(30), Err(AVLTreeError::NodeNotFound));
+        assert_eq!(tree.remove(50), Ok(50));
+        assert_eq!(tree.remove(20), Ok(20));
+        assert_eq!(tree.size(), 7);
+
+        let keys: Vec<i32> = tree.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, vec![35, 40, 45, 60, 65, 70, 80]);
+
+        tree.clear();
+        assert_eq!(tree.remove(35), Err(AVLTreeError::EmptyAVLTree));
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut tree = AVLTree::new();
+        tree.insert("a", 1).unwrap();
+
+        assert_eq!(tree.replace("a", 2), Ok(1));
+        assert_eq!(tree.get("a"), Ok(2));
+        assert_eq!(tree.replace("b", 3), Err(AVLTreeError::NodeNotFound));
+    }
+
+    #[test]
+    fn test_first_last() {
+        let mut tree: AVLTree<i32, char> = AVLTree::new();
+        assert_eq!(tree.first(), Err(AVLTreeError::EmptyAVLTree));
+
+        for (key, value) in [(3, 'c'), (1, 'a'), (4, 'd'), (2, 'b')] {
+            tree.insert(key, value).unwrap();
+        }
+
+        assert_eq!(tree.first(), Ok((1, 'a')));
+        assert_eq!(tree.last(), Ok((4, 'd')));
+    }
+
+    #[test]
+    fn test_range() {
+        let mut tree = AVLTree::new();
+        for key in (0..20).rev() {
+            tree.insert(key, key * 10).unwrap();
+        }
+
+        let range: Vec<(i32, i32)> = tree.range(5..8).collect();
+        assert_eq!(range, vec![(5, 50), (6, 60), (7, 70)]);
+
+        let range: Vec<i32> = tree.range(17..).map(|(key, _)| key).collect();
+        assert_eq!(range, vec![17, 18, 19]);
+
+        let range: Vec<i32> = tree.range(..=2).map(|(key, _)| key).collect();
+        assert_eq!(range, vec![0, 1, 2]);
+
+        use std::ops::Bound;
+        let range: Vec<i32> = tree
+            .range((Bound::Excluded(3), Bound::Excluded(6)))
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(range, vec![4, 5]);
+
+        assert_eq!(tree.range(30..).count(), 0);
+        assert_eq!(tree.iter().count(), 20);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut tree = AVLTree::new();
+
+        assert_eq!(tree.get(1), Err(AVLTreeError::EmptyAVLTree));
+
+        tree.insert(5, String::from("five")).unwrap();
+        tree.insert(3, String::from("three")).unwrap();
+
+        assert_eq!(
+            tree.insert(5, String::from("cinq")),
+            Err(AVLTreeError::AlreadyExists {
+                key: 5,
+                value: String::from("cinq")
+            })
+        );
+        assert_eq!(tree.get(4), Err(AVLTreeError::NodeNotFound));
+        assert_eq!(tree.get(5), Ok(String::from("five")));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err: AVLTreeError<i32, &str> = AVLTreeError::AlreadyExists {
+            key: 1,
+            value: "one",
+        };
+        assert_eq!(err.to_string(), "a node with the given key already exists");
+
+        let err: Box<dyn std::error::Error> = Box::new(err);
+        assert_eq!(err.to_string(), "a node with the given key already exists");
     }
 }
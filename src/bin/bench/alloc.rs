@@ -0,0 +1,128 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+// Counts every allocation made by the current thread. The counters are
+// thread-local so parallel test threads cannot skew a measurement, and they
+// use a const initializer so touching them never allocates.
+pub struct CountingAllocator;
+
+struct Counters {
+    allocations: Cell<usize>,
+    deallocations: Cell<usize>,
+    current: Cell<usize>,
+    peak: Cell<usize>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            deallocations: Cell::new(0),
+            current: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+fn record(allocated: usize, freed: usize, allocations: usize, deallocations: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        counters
+            .allocations
+            .set(counters.allocations.get() + allocations);
+        counters
+            .deallocations
+            .set(counters.deallocations.get() + deallocations);
+
+        let current = (counters.current.get() + allocated).saturating_sub(freed);
+        counters.current.set(current);
+        counters.peak.set(counters.peak.get().max(current));
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0, 1, 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0, 1, 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size(), 0, 1);
+    }
+
+    // A reallocation counts as one allocation and one deallocation, which is
+    // what a growing Vec or VecDeque costs compared to a node-based structure.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size(), 1, 1);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    pub allocations: usize,
+    pub deallocations: usize,
+    pub current: usize,
+    pub peak: usize,
+}
+
+pub fn snapshot() -> Snapshot {
+    COUNTERS.with(|counters| Snapshot {
+        allocations: counters.allocations.get(),
+        deallocations: counters.deallocations.get(),
+        current: counters.current.get(),
+        peak: counters.peak.get(),
+    })
+}
+
+// Restarts peak tracking from the bytes that are live right now.
+pub fn reset_peak() {
+    COUNTERS.with(|counters| counters.peak.set(counters.current.get()));
+}
+
+#[cfg(test)]
+mod alloc_tests {
+    use std::hint::black_box;
+
+    use super::{reset_peak, snapshot};
+
+    #[test]
+    fn test_counts() {
+        reset_peak();
+        let before = snapshot();
+
+        // `black_box` keeps release builds from eliding the allocations.
+        let boxed = black_box(Box::new([0u64; 16]));
+        let mut vec: Vec<u8> = black_box(Vec::with_capacity(8));
+        vec.extend_from_slice(black_box(&[1; 64]));
+        let during = snapshot();
+        black_box(&boxed);
+        black_box(&vec);
+        drop(boxed);
+        drop(vec);
+        let after = snapshot();
+
+        assert_eq!(during.allocations - before.allocations, 3);
+        assert_eq!(during.deallocations - before.deallocations, 1);
+        assert_eq!(during.current - before.current, 128 + 64);
+        assert!(during.peak - before.current >= 128 + 64);
+        assert_eq!(after.deallocations - before.deallocations, 3);
+        assert_eq!(after.current, before.current);
+    }
+}
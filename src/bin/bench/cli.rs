@@ -0,0 +1,273 @@
+use std::{fmt, path::PathBuf, time::Duration};
+
+use crate::{
+    harness::Config,
+    workload::{Operation, Structure},
+};
+
+pub const NUMS: [usize; 4] = [25, 250, 2500, 25000];
+
+pub const USAGE: &str = "\
+Usage: bench [OPTIONS]
+
+Options:
+  -s, --structures <LIST>  Structures to run, comma separated (default: all)
+  -o, --ops <LIST>         Operations to run, comma separated (default: all)
+  -n, --sizes <LIST>       Element counts, comma separated (default: 25,250,2500,25000)
+  -r, --samples <N>        Samples taken per workload (default: 20)
+  -w, --warmup <N>         Untimed warm-up runs per workload (default: 3)
+  -t, --sample-time <MS>   Minimum duration of one sample in milliseconds (default: 5)
+      --seed <N>           Seed for the random input (default: random)
+      --trace <PATH>       Replay a recorded trace instead of the built-in operations
+      --json <PATH>        Write the results as JSON
+      --csv <PATH>         Write the results as CSV
+      --compare <PATH>     Compare against a JSON baseline and fail on regressions
+      --threshold <PCT>    Slowdown in percent that counts as a regression (default: 5)
+      --list               Print the available workloads and exit
+  -h, --help               Print this message and exit";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run(Box<Options>),
+    List,
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub structures: Vec<Structure>,
+    pub operations: Vec<Operation>,
+    pub sizes: Vec<usize>,
+    pub seed: Option<u64>,
+    pub trace: Option<PathBuf>,
+    pub config: Config,
+    pub json: Option<PathBuf>,
+    pub csv: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    pub threshold: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            structures: Structure::ALL.to_vec(),
+            operations: Operation::ALL.to_vec(),
+            sizes: NUMS.to_vec(),
+            seed: None,
+            trace: None,
+            config: Config::default(),
+            json: None,
+            csv: None,
+            compare: None,
+            threshold: 5.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    MissingValue(String),
+    InvalidValue { flag: String, value: String },
+    UnknownFlag(String),
+    // An operation picked with `--ops` that none of the structures support.
+    Inapplicable(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::MissingValue(flag) => write!(f, "{flag} expects a value"),
+            CliError::InvalidValue { flag, value } => {
+                write!(f, "invalid value `{value}` for {flag}")
+            }
+            CliError::UnknownFlag(flag) => write!(f, "unknown flag `{flag}`"),
+            CliError::Inapplicable(operation) => {
+                write!(f, "{operation} does not apply to any selected structure")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+pub fn parse<I>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    let mut picked_ops = false;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--list" => return Ok(Command::List),
+            "-s" | "--structures" => {
+                options.structures = parse_list(&flag, args.next(), Structure::from_name)?
+            }
+            "-o" | "--ops" => {
+                options.operations = parse_list(&flag, args.next(), Operation::from_name)?;
+                picked_ops = true;
+            }
+            "-n" | "--sizes" => {
+                options.sizes = parse_list(&flag, args.next(), |size| size.parse().ok())?
+            }
+            "-r" | "--samples" => {
+                options.config.samples = parse_value(&flag, args.next(), |samples| {
+                    samples.parse().ok().filter(|samples| *samples > 0)
+                })?
+            }
+            "-w" | "--warmup" => {
+                options.config.warmup = parse_value(&flag, args.next(), |n| n.parse().ok())?
+            }
+            "-t" | "--sample-time" => {
+                options.config.sample_time = parse_value(&flag, args.next(), |ms| {
+                    ms.parse().ok().map(Duration::from_millis)
+                })?
+            }
+            "--seed" => options.seed = Some(parse_value(&flag, args.next(), |s| s.parse().ok())?),
+            "--trace" => options.trace = Some(parse_value(&flag, args.next(), parse_path)?),
+            "--json" => options.json = Some(parse_value(&flag, args.next(), parse_path)?),
+            "--csv" => options.csv = Some(parse_value(&flag, args.next(), parse_path)?),
+            "--compare" => options.compare = Some(parse_value(&flag, args.next(), parse_path)?),
+            "--threshold" => {
+                options.threshold = parse_value(&flag, args.next(), |percent| {
+                    percent.parse().ok().filter(|percent: &f64| *percent >= 0.0)
+                })?
+            }
+            _ => return Err(CliError::UnknownFlag(flag)),
+        }
+    }
+
+    // Traces replace the operations, so there is nothing to check then.
+    if picked_ops && options.trace.is_none() {
+        let unused = options.operations.iter().find(|operation| {
+            !options
+                .structures
+                .iter()
+                .any(|&structure| operation.applies_to(structure))
+        });
+        if let Some(operation) = unused {
+            return Err(CliError::Inapplicable(operation.name().to_string()));
+        }
+    }
+
+    Ok(Command::Run(Box::new(options)))
+}
+
+fn parse_path(path: &str) -> Option<PathBuf> {
+    Some(PathBuf::from(path))
+}
+
+fn parse_value<T, F>(flag: &str, value: Option<String>, parse: F) -> Result<T, CliError>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    let value = value.ok_or_else(|| CliError::MissingValue(flag.to_string()))?;
+
+    parse(&value).ok_or_else(|| CliError::InvalidValue {
+        flag: flag.to_string(),
+        value,
+    })
+}
+
+fn parse_list<T, F>(flag: &str, value: Option<String>, parse: F) -> Result<Vec<T>, CliError>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    parse_value(flag, value, |list| {
+        list.split(',')
+            .map(|item| parse(item.trim()))
+            .collect::<Option<Vec<T>>>()
+            .filter(|items| !items.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{parse, CliError, Command};
+    use crate::workload::{Operation, Structure};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(parse(args("")), Ok(Command::Run(Box::default())));
+        assert_eq!(parse(args("--list")), Ok(Command::List));
+        assert_eq!(parse(args("-r 3 --help")), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_flags() {
+        let Ok(Command::Run(options)) = parse(args(
+            "-s linked-list,vec-deque --ops push-back,pop-front -n 10,20 -r 3 -w 0 -t 2 --seed 42",
+        )) else {
+            panic!("expected a run command");
+        };
+
+        assert_eq!(
+            options.structures,
+            vec![Structure::LinkedList, Structure::VecDeque]
+        );
+        assert_eq!(
+            options.operations,
+            vec![Operation::PushBack, Operation::PopFront]
+        );
+        assert_eq!(options.sizes, vec![10, 20]);
+        assert_eq!(options.config.samples, 3);
+        assert_eq!(options.config.warmup, 0);
+        assert_eq!(options.config.sample_time, Duration::from_millis(2));
+        assert_eq!(options.seed, Some(42));
+    }
+
+    #[test]
+    fn test_output_flags() {
+        let Ok(Command::Run(options)) = parse(args(
+            "--json out.json --csv out.csv --compare base.json --threshold 7.5 --trace ops.trace",
+        )) else {
+            panic!("expected a run command");
+        };
+
+        assert_eq!(options.json, Some(PathBuf::from("out.json")));
+        assert_eq!(options.csv, Some(PathBuf::from("out.csv")));
+        assert_eq!(options.compare, Some(PathBuf::from("base.json")));
+        assert_eq!(options.threshold, 7.5);
+        assert_eq!(options.trace, Some(PathBuf::from("ops.trace")));
+        assert!(parse(args("--threshold -1")).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse(args("--sizes")),
+            Err(CliError::MissingValue(String::from("--sizes")))
+        );
+        assert_eq!(
+            parse(args("-s heap")),
+            Err(CliError::InvalidValue {
+                flag: String::from("-s"),
+                value: String::from("heap")
+            })
+        );
+        assert_eq!(
+            parse(args("-r 0")),
+            Err(CliError::InvalidValue {
+                flag: String::from("-r"),
+                value: String::from("0")
+            })
+        );
+        assert_eq!(
+            parse(args("--fast")),
+            Err(CliError::UnknownFlag(String::from("--fast")))
+        );
+        assert_eq!(
+            parse(args("-s avl-tree,hash-map -o insert,from-iter")),
+            Err(CliError::Inapplicable(String::from("from-iter")))
+        );
+        assert!(parse(args("-s avl-tree,vec-deque -o from-iter")).is_ok());
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::alloc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub warmup: usize,
+    pub samples: usize,
+    pub sample_time: Duration,
+    // Caps how many elements a batch of prepared inputs may hold at once.
+    pub max_batch_elements: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            warmup: 3,
+            samples: 20,
+            sample_time: Duration::from_millis(5),
+            max_batch_elements: 4_000_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    // Nanoseconds per iteration, one figure per sample.
+    pub samples: Vec<f64>,
+    pub memory: Memory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Memory {
+    // Made by the routine itself, not by its setup.
+    pub allocations: usize,
+    pub deallocations: usize,
+    // Most bytes live at once across setup and routine. Inputs the routine
+    // consumes count too, so a workload that builds its container from one
+    // has to take it back out to report the container alone.
+    pub peak_bytes: usize,
+    pub bytes_per_element: f64,
+}
+
+// Each sample runs the routine enough times to fill `sample_time`, so cheap
+// routines are not lost in timer resolution. Setup and dropping outputs stay
+// off the clock. One extra untimed run is made to profile allocations.
+pub fn bench<S, O, F, R>(config: &Config, size: usize, mut setup: F, mut routine: R) -> Measurement
+where
+    F: FnMut() -> S,
+    R: FnMut(S) -> O,
+{
+    let mut estimate = Duration::ZERO;
+    for _ in 0..config.warmup.max(1) {
+        estimate = run_batch(1, &mut setup, &mut routine);
+    }
+
+    let max_iters = (config.max_batch_elements / size.max(1)).max(1);
+    let iters = (config.sample_time.as_nanos() / estimate.as_nanos().max(1))
+        .clamp(1, max_iters as u128) as usize;
+
+    let samples = (0..config.samples)
+        .map(|_| run_batch(iters, &mut setup, &mut routine).as_nanos() as f64 / iters as f64)
+        .collect();
+
+    Measurement {
+        samples,
+        memory: profile(size, &mut setup, &mut routine),
+    }
+}
+
+fn profile<S, O, F, R>(size: usize, setup: &mut F, routine: &mut R) -> Memory
+where
+    F: FnMut() -> S,
+    R: FnMut(S) -> O,
+{
+    alloc::reset_peak();
+    let start = alloc::snapshot();
+
+    let input = setup();
+    let before = alloc::snapshot();
+    let output = black_box(routine(black_box(input)));
+    let after = alloc::snapshot();
+    drop(output);
+
+    let peak_bytes = after.peak - start.current;
+    Memory {
+        allocations: after.allocations - before.allocations,
+        deallocations: after.deallocations - before.deallocations,
+        peak_bytes,
+        bytes_per_element: peak_bytes as f64 / size.max(1) as f64,
+    }
+}
+
+fn run_batch<S, O, F, R>(iters: usize, setup: &mut F, routine: &mut R) -> Duration
+where
+    F: FnMut() -> S,
+    R: FnMut(S) -> O,
+{
+    let inputs: Vec<S> = (0..iters).map(|_| setup()).collect();
+    let mut outputs = Vec::with_capacity(iters);
+
+    let start = Instant::now();
+    for input in inputs {
+        outputs.push(black_box(routine(black_box(input))));
+    }
+    let elapsed = start.elapsed();
+
+    drop(outputs);
+    elapsed
+}
+
+#[cfg(test)]
+mod harness_tests {
+    use std::time::Duration;
+
+    use super::{bench, Config};
+
+    #[test]
+    fn test_bench() {
+        let config = Config {
+            warmup: 2,
+            samples: 7,
+            sample_time: Duration::from_micros(50),
+            max_batch_elements: 1000,
+        };
+
+        let mut setups = 0;
+        let measurement = bench(
+            &config,
+            10,
+            || {
+                setups += 1;
+                vec![1u64; 10]
+            },
+            |values| values.iter().sum::<u64>(),
+        );
+
+        assert_eq!(measurement.samples.len(), 7);
+        assert!(measurement.samples.iter().all(|sample| *sample >= 0.0));
+        assert!(setups > 2 + 7);
+        assert!(setups <= 2 + 7 * 100 + 1);
+    }
+
+    #[test]
+    fn test_memory() {
+        let config = Config {
+            warmup: 1,
+            samples: 1,
+            sample_time: Duration::from_micros(1),
+            max_batch_elements: 10,
+        };
+
+        let measurement = bench(
+            &config,
+            100,
+            || Vec::<Box<u64>>::with_capacity(100),
+            |mut values| {
+                for value in 0..100 {
+                    values.push(Box::new(value));
+                }
+                values
+            },
+        );
+
+        let memory = measurement.memory;
+        assert_eq!(memory.allocations, 100);
+        assert_eq!(memory.deallocations, 0);
+        assert_eq!(memory.peak_bytes, 100 * 8 + 100 * 8);
+        assert_eq!(memory.bytes_per_element, 16.0);
+    }
+}
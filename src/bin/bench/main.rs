@@ -0,0 +1,224 @@
+use std::{error::Error, fs, process::ExitCode};
+
+use data_structs::trace::Trace;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    cli::{Command, Options, USAGE},
+    harness::Measurement,
+    report::{Environment, Record, Report},
+    stats::Stats,
+    workload::{Operation, Structure},
+};
+
+mod alloc;
+mod cli;
+mod harness;
+mod json;
+mod report;
+mod stats;
+mod workload;
+
+#[global_allocator]
+static GLOBAL: alloc::CountingAllocator = alloc::CountingAllocator;
+
+fn main() -> ExitCode {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command {
+        Command::Help => println!("{USAGE}"),
+        Command::List => list(),
+        Command::Run(options) => {
+            return match run(&options) {
+                Ok(code) => code,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    ExitCode::FAILURE
+                }
+            };
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn list() {
+    println!("Structures:");
+    for structure in Structure::ALL {
+        let operations: Vec<&str> = Operation::ALL
+            .into_iter()
+            .filter(|operation| operation.applies_to(structure))
+            .map(Operation::name)
+            .collect();
+        println!("  {:<16} {}", structure.name(), operations.join(", "));
+    }
+
+    println!("\nOperations:");
+    for operation in Operation::ALL {
+        println!("  {:<16} {}", operation.name(), operation.description());
+    }
+}
+
+fn run(options: &Options) -> Result<ExitCode, Box<dyn Error>> {
+    // Read the baseline up front so a bad path fails before a long run.
+    let baseline = match &options.compare {
+        Some(path) => Some(Report::from_json(&fs::read_to_string(path)?)?),
+        None => None,
+    };
+    let trace: Option<Trace<u64>> = match &options.trace {
+        Some(path) => Some(fs::read_to_string(path)?.parse()?),
+        None => None,
+    };
+    if let Some(trace) = &trace {
+        if !trace.is_deque_trace() && !trace.is_map_trace() {
+            return Err("the trace mixes deque and map operations".into());
+        }
+    }
+
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let max_size = options.sizes.iter().copied().max().unwrap_or(0);
+    let vector: Vec<u64> = (0..max_size).map(|_| rng.gen()).collect();
+
+    println!(
+        "seed: {seed}, samples: {}, warmup: {}, sample time: {:?}\n",
+        options.config.samples, options.config.warmup, options.config.sample_time
+    );
+    println!(
+        "{:<16} {:<11} {:>6} {:>13} {:>7} {:>13} {:>12} {:>13} {:>13} {:>8} {:>8} {:>11} {:>7}",
+        "structure",
+        "operation",
+        "size",
+        "mean (ns)",
+        "ci95",
+        "median",
+        "stddev",
+        "p95",
+        "p99",
+        "allocs",
+        "deallocs",
+        "peak (B)",
+        "B/elem"
+    );
+
+    let mut report = Report {
+        environment: Environment::capture(seed, &options.config),
+        records: Vec::new(),
+    };
+
+    if let Some(trace) = &trace {
+        for &structure in &options.structures {
+            if workload::can_replay(structure, trace) {
+                let measurement = workload::replay(structure, trace, &options.config)?;
+                add_record(&mut report, structure, "replay", trace.len(), measurement);
+            }
+        }
+    } else {
+        for &structure in &options.structures {
+            for &operation in &options.operations {
+                if !operation.applies_to(structure) {
+                    continue;
+                }
+
+                for &size in &options.sizes {
+                    let measurement =
+                        workload::run(structure, operation, &vector[..size], &options.config);
+                    add_record(&mut report, structure, operation.name(), size, measurement);
+                }
+            }
+        }
+    }
+
+    if let Some(path) = &options.json {
+        fs::write(path, report.to_json())?;
+    }
+    if let Some(path) = &options.csv {
+        fs::write(path, report.to_csv())?;
+    }
+
+    let Some(baseline) = baseline else {
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    if baseline.environment.arch != report.environment.arch
+        || baseline.environment.cpu_model != report.environment.cpu_model
+        || baseline.environment.profile != report.environment.profile
+    {
+        println!("\nwarning: the baseline was recorded on a different machine or profile");
+    }
+
+    let comparisons = report::compare(&baseline, &report, options.threshold);
+    println!(
+        "\n{:<16} {:<11} {:>6} {:>13} {:>13} {:>9}",
+        "structure", "operation", "size", "baseline", "current", "change"
+    );
+    for comparison in &comparisons {
+        println!(
+            "{:<16} {:<11} {:>6} {:>13.1} {:>13.1} {:>+8.1}%{}",
+            comparison.structure,
+            comparison.operation,
+            comparison.size,
+            comparison.baseline_ns,
+            comparison.current_ns,
+            comparison.change_percent,
+            if comparison.regressed {
+                "  REGRESSED"
+            } else {
+                ""
+            }
+        );
+    }
+
+    let regressions = comparisons.iter().filter(|c| c.regressed).count();
+    if regressions > 0 {
+        println!(
+            "\n{regressions} workload(s) slower than the baseline by more than {}%",
+            options.threshold
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+
+    println!("\nno regressions above {}%", options.threshold);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn add_record(
+    report: &mut Report,
+    structure: Structure,
+    operation: &str,
+    size: usize,
+    measurement: Measurement,
+) {
+    let stats = Stats::from_samples(&measurement.samples);
+    let memory = measurement.memory;
+    println!(
+        "{:<16} {:<11} {:>6} {:>13.1} {:>6.1}% {:>13.1} {:>12.1} {:>13.1} {:>13.1} {:>8} {:>8} {:>11} {:>7.1}",
+        structure.name(),
+        operation,
+        size,
+        stats.mean,
+        stats.ci95_percent(),
+        stats.median,
+        stats.stddev,
+        stats.p95,
+        stats.p99,
+        memory.allocations,
+        memory.deallocations,
+        memory.peak_bytes,
+        memory.bytes_per_element
+    );
+
+    report.records.push(Record {
+        structure: structure.name().to_string(),
+        operation: operation.to_string(),
+        size,
+        stats,
+        memory: Some(memory),
+    });
+}
@@ -0,0 +1,427 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    harness::{Config, Memory},
+    json::{self, Value},
+    stats::Stats,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Environment {
+    pub version: String,
+    pub profile: String,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    pub cpu_model: String,
+    pub timestamp: u64,
+    pub seed: u64,
+    pub samples: usize,
+    pub warmup: usize,
+    pub sample_time_ns: u64,
+}
+
+impl Environment {
+    pub fn capture(seed: u64, config: &Config) -> Environment {
+        Environment {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            profile: String::from(if cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            }),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism().map_or(1, usize::from),
+            cpu_model: cpu_model().unwrap_or_else(|| String::from("unknown")),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            seed,
+            samples: config.samples,
+            warmup: config.warmup,
+            sample_time_ns: config.sample_time.as_nanos() as u64,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            field_str("version", &self.version),
+            field_str("profile", &self.profile),
+            field_str("os", &self.os),
+            field_str("arch", &self.arch),
+            field_num("cpus", self.cpus as f64),
+            field_str("cpu_model", &self.cpu_model),
+            field_num("timestamp", self.timestamp as f64),
+            // Seeds use the full u64 range, which a JSON number cannot hold.
+            field_str("seed", &self.seed.to_string()),
+            field_num("samples", self.samples as f64),
+            field_num("warmup", self.warmup as f64),
+            field_num("sample_time_ns", self.sample_time_ns as f64),
+        ])
+    }
+
+    fn from_json(value: &Value) -> Result<Environment, ReportError> {
+        Ok(Environment {
+            version: read_str(value, "version")?,
+            profile: read_str(value, "profile")?,
+            os: read_str(value, "os")?,
+            arch: read_str(value, "arch")?,
+            cpus: read_num(value, "cpus")? as usize,
+            cpu_model: read_str(value, "cpu_model")?,
+            timestamp: read_num(value, "timestamp")? as u64,
+            seed: read_str(value, "seed")?
+                .parse()
+                .map_err(|_| ReportError::MissingField("seed"))?,
+            samples: read_num(value, "samples")? as usize,
+            warmup: read_num(value, "warmup")? as usize,
+            sample_time_ns: read_num(value, "sample_time_ns")? as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub structure: String,
+    pub operation: String,
+    pub size: usize,
+    pub stats: Stats,
+    // Absent in reports written before allocation profiling existed.
+    pub memory: Option<Memory>,
+}
+
+impl Record {
+    fn to_json(&self) -> Value {
+        let stats = &self.stats;
+        let mut fields = vec![
+            field_str("structure", &self.structure),
+            field_str("operation", &self.operation),
+            field_num("size", self.size as f64),
+            field_num("samples", stats.samples as f64),
+            field_num("mean_ns", stats.mean),
+            field_num("median_ns", stats.median),
+            field_num("stddev_ns", stats.stddev),
+            field_num("min_ns", stats.min),
+            field_num("max_ns", stats.max),
+            field_num("p95_ns", stats.p95),
+            field_num("p99_ns", stats.p99),
+        ];
+
+        if let Some(memory) = &self.memory {
+            fields.extend([
+                field_num("allocations", memory.allocations as f64),
+                field_num("deallocations", memory.deallocations as f64),
+                field_num("peak_bytes", memory.peak_bytes as f64),
+                field_num("bytes_per_element", memory.bytes_per_element),
+            ]);
+        }
+
+        Value::Object(fields)
+    }
+
+    fn from_json(value: &Value) -> Result<Record, ReportError> {
+        Ok(Record {
+            structure: read_str(value, "structure")?,
+            operation: read_str(value, "operation")?,
+            size: read_num(value, "size")? as usize,
+            stats: Stats {
+                samples: read_num(value, "samples")? as usize,
+                mean: read_num(value, "mean_ns")?,
+                median: read_num(value, "median_ns")?,
+                stddev: read_num(value, "stddev_ns")?,
+                min: read_num(value, "min_ns")?,
+                max: read_num(value, "max_ns")?,
+                p95: read_num(value, "p95_ns")?,
+                p99: read_num(value, "p99_ns")?,
+            },
+            memory: match value.get("allocations") {
+                Some(_) => Some(Memory {
+                    allocations: read_num(value, "allocations")? as usize,
+                    deallocations: read_num(value, "deallocations")? as usize,
+                    peak_bytes: read_num(value, "peak_bytes")? as usize,
+                    bytes_per_element: read_num(value, "bytes_per_element")?,
+                }),
+                None => None,
+            },
+        })
+    }
+
+    fn key(&self) -> (&str, &str, usize) {
+        (&self.structure, &self.operation, self.size)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub environment: Environment,
+    pub records: Vec<Record>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        Value::Object(vec![
+            (String::from("environment"), self.environment.to_json()),
+            (
+                String::from("results"),
+                Value::Array(self.records.iter().map(Record::to_json).collect()),
+            ),
+        ])
+        .to_pretty_string()
+    }
+
+    pub fn from_json(input: &str) -> Result<Report, ReportError> {
+        let value = json::parse(input)?;
+        let environment = value
+            .get("environment")
+            .ok_or(ReportError::MissingField("environment"))?;
+        let records = value
+            .get("results")
+            .and_then(Value::as_array)
+            .ok_or(ReportError::MissingField("results"))?;
+
+        Ok(Report {
+            environment: Environment::from_json(environment)?,
+            records: records
+                .iter()
+                .map(Record::from_json)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn to_csv(&self) -> String {
+        let env = &self.environment;
+        let mut out = String::from(
+            "structure,operation,size,samples,mean_ns,ci95_percent,median_ns,stddev_ns,\
+             min_ns,max_ns,p95_ns,p99_ns,allocations,deallocations,peak_bytes,bytes_per_element,\
+             version,profile,os,arch,cpus,cpu_model,timestamp,seed\n",
+        );
+
+        for record in &self.records {
+            let stats = &record.stats;
+            let memory = match &record.memory {
+                Some(memory) => format!(
+                    "{},{},{},{:.3}",
+                    memory.allocations,
+                    memory.deallocations,
+                    memory.peak_bytes,
+                    memory.bytes_per_element
+                ),
+                None => String::from(",,,"),
+            };
+            out.push_str(&format!(
+                "{},{},{},{},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{},{},{},{},{},{},{},{},{}\n",
+                record.structure,
+                record.operation,
+                record.size,
+                stats.samples,
+                stats.mean,
+                stats.ci95_percent(),
+                stats.median,
+                stats.stddev,
+                stats.min,
+                stats.max,
+                stats.p95,
+                stats.p99,
+                memory,
+                env.version,
+                env.profile,
+                env.os,
+                env.arch,
+                env.cpus,
+                csv_escape(&env.cpu_model),
+                env.timestamp,
+                env.seed,
+            ));
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub structure: String,
+    pub operation: String,
+    pub size: usize,
+    pub baseline_ns: f64,
+    pub current_ns: f64,
+    pub change_percent: f64,
+    pub regressed: bool,
+}
+
+// Pairs up workloads present in both reports and flags those whose mean got
+// slower by more than `threshold_percent`.
+pub fn compare(baseline: &Report, current: &Report, threshold_percent: f64) -> Vec<Comparison> {
+    current
+        .records
+        .iter()
+        .filter_map(|record| {
+            let old = baseline
+                .records
+                .iter()
+                .find(|old| old.key() == record.key())?;
+            let change_percent = if old.stats.mean > 0.0 {
+                (record.stats.mean - old.stats.mean) / old.stats.mean * 100.0
+            } else {
+                0.0
+            };
+
+            Some(Comparison {
+                structure: record.structure.clone(),
+                operation: record.operation.clone(),
+                size: record.size,
+                baseline_ns: old.stats.mean,
+                current_ns: record.stats.mean,
+                change_percent,
+                regressed: change_percent > threshold_percent,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportError {
+    Json(json::ParseError),
+    MissingField(&'static str),
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportError::Json(err) => write!(f, "{err}"),
+            ReportError::MissingField(field) => {
+                write!(f, "report is missing the `{field}` field")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+impl From<json::ParseError> for ReportError {
+    fn from(value: json::ParseError) -> Self {
+        ReportError::Json(value)
+    }
+}
+
+fn field_str(name: &str, value: &str) -> (String, Value) {
+    (name.to_string(), Value::String(value.to_string()))
+}
+
+fn field_num(name: &str, value: f64) -> (String, Value) {
+    (name.to_string(), Value::Number(value))
+}
+
+fn read_str(value: &Value, name: &'static str) -> Result<String, ReportError> {
+    value
+        .get(name)
+        .and_then(Value::as_str)
+        .map(String::from)
+        .ok_or(ReportError::MissingField(name))
+}
+
+fn read_num(value: &Value, name: &'static str) -> Result<f64, ReportError> {
+    value
+        .get(name)
+        .and_then(Value::as_f64)
+        .ok_or(ReportError::MissingField(name))
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split(':').nth(1))
+        .map(|model| model.trim().to_string())
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::{compare, Environment, Record, Report, ReportError};
+    use crate::{
+        harness::{Config, Memory},
+        stats::Stats,
+    };
+
+    fn report(means: &[(&str, f64)]) -> Report {
+        Report {
+            environment: Environment::capture(u64::MAX - 1, &Config::default()),
+            records: means
+                .iter()
+                .map(|(operation, mean)| Record {
+                    structure: String::from("linked-list"),
+                    operation: operation.to_string(),
+                    size: 25,
+                    stats: Stats::from_samples(&[*mean * 0.9, *mean, *mean * 1.1]),
+                    memory: Some(Memory {
+                        allocations: 25,
+                        deallocations: 0,
+                        peak_bytes: 1000,
+                        bytes_per_element: 40.0,
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let report = report(&[("push-back", 100.0), ("pop-front", 55.5)]);
+        let parsed = Report::from_json(&report.to_json()).unwrap();
+
+        assert_eq!(parsed.environment, report.environment);
+        assert_eq!(parsed.records.len(), 2);
+        for (parsed, record) in parsed.records.iter().zip(&report.records) {
+            assert_eq!(parsed.key(), record.key());
+            assert!((parsed.stats.mean - record.stats.mean).abs() < 1e-9);
+            assert!((parsed.stats.stddev - record.stats.stddev).abs() < 1e-9);
+            assert_eq!(parsed.memory, record.memory);
+        }
+
+        let mut old = report.clone();
+        old.records[0].memory = None;
+        let parsed = Report::from_json(&old.to_json()).unwrap();
+        assert_eq!(parsed.records[0].memory, None);
+
+        assert_eq!(
+            Report::from_json("{\"results\": []}"),
+            Err(ReportError::MissingField("environment"))
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = report(&[("push-back", 100.0)]).to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("structure,operation,size,samples,mean_ns"));
+        assert!(lines[1].starts_with("linked-list,push-back,25,3,100.000,"));
+        assert!(lines[1].contains(",25,0,1000,40.000,"));
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = report(&[("push-back", 100.0), ("pop-front", 100.0), ("new", 5.0)]);
+        let current = report(&[("push-back", 104.0), ("pop-front", 120.0), ("get", 1.0)]);
+
+        let comparisons = compare(&baseline, &current, 5.0);
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].regressed);
+        assert!((comparisons[0].change_percent - 4.0).abs() < 1e-9);
+        assert!(comparisons[1].regressed);
+        assert!((comparisons[1].change_percent - 20.0).abs() < 1e-9);
+    }
+}
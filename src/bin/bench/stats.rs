@@ -0,0 +1,89 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[f64]) -> Stats {
+        assert!(!samples.is_empty(), "cannot summarize zero samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Stats {
+            samples: sorted.len(),
+            mean,
+            median: percentile(&sorted, 50.0),
+            stddev: variance.sqrt(),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            p95: percentile(&sorted, 95.0),
+            p99: percentile(&sorted, 99.0),
+        }
+    }
+
+    // Half-width of the 95% confidence interval of the mean, as a percentage
+    // of the mean. Two results whose intervals overlap are not distinguishable.
+    pub fn ci95_percent(&self) -> f64 {
+        if self.mean == 0.0 {
+            return 0.0;
+        }
+
+        1.96 * self.stddev / (self.samples as f64).sqrt() / self.mean * 100.0
+    }
+}
+
+// Linear interpolation between the closest ranks of an already sorted slice.
+fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    let rank = percent / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::Stats;
+
+    #[test]
+    fn test_from_samples() {
+        let samples: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.mean, 50.5);
+        assert_eq!(stats.median, 50.5);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 100.0);
+        assert!((stats.p95 - 95.05).abs() < 1e-9);
+        assert!((stats.p99 - 99.01).abs() < 1e-9);
+        assert!((stats.stddev - 29.011_491).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_single_sample() {
+        let stats = Stats::from_samples(&[42.0]);
+
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.p99, 42.0);
+        assert_eq!(stats.stddev, 0.0);
+        assert_eq!(stats.ci95_percent(), 0.0);
+    }
+}
@@ -0,0 +1,606 @@
+use std::{
+    collections::{self, BTreeMap, HashMap, VecDeque},
+    fmt,
+    hash::Hash,
+    mem,
+};
+
+use data_structs::{
+    avl_tree::AVLTree,
+    linked_list::LinkedList,
+    trace::{self, ReplayError, Trace},
+    traits::{Deque, OrderedMap},
+    unrolled_list::UnrolledList,
+};
+
+use crate::harness::{bench, Config, Measurement};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Sequence,
+    OrderedMap,
+    HashMap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Structure {
+    LinkedList,
+    UnrolledList,
+    StdLinkedList,
+    VecDeque,
+    AVLTree,
+    BTreeMap,
+    HashMap,
+    AVLTreeString,
+    BTreeMapString,
+    HashMapString,
+}
+
+impl Structure {
+    pub const ALL: [Structure; 10] = [
+        Structure::LinkedList,
+        Structure::UnrolledList,
+        Structure::StdLinkedList,
+        Structure::VecDeque,
+        Structure::AVLTree,
+        Structure::BTreeMap,
+        Structure::HashMap,
+        Structure::AVLTreeString,
+        Structure::BTreeMapString,
+        Structure::HashMapString,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Structure::LinkedList => "linked-list",
+            Structure::UnrolledList => "unrolled-list",
+            Structure::StdLinkedList => "std-linked-list",
+            Structure::VecDeque => "vec-deque",
+            Structure::AVLTree => "avl-tree",
+            Structure::BTreeMap => "btree-map",
+            Structure::HashMap => "hash-map",
+            Structure::AVLTreeString => "avl-tree-string",
+            Structure::BTreeMapString => "btree-map-string",
+            Structure::HashMapString => "hash-map-string",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Structure> {
+        Structure::ALL
+            .into_iter()
+            .find(|structure| structure.name() == name)
+    }
+
+    pub fn kind(self) -> Kind {
+        match self {
+            Structure::LinkedList
+            | Structure::UnrolledList
+            | Structure::StdLinkedList
+            | Structure::VecDeque => Kind::Sequence,
+            Structure::AVLTree
+            | Structure::BTreeMap
+            | Structure::AVLTreeString
+            | Structure::BTreeMapString => Kind::OrderedMap,
+            Structure::HashMap | Structure::HashMapString => Kind::HashMap,
+        }
+    }
+}
+
+impl fmt::Display for Structure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    New,
+    FromIter,
+    PushBack,
+    PushFront,
+    PopBack,
+    PopFront,
+    Insert,
+    InsertSeq,
+    Get,
+    GetMiss,
+    Remove,
+    Range,
+    Iter,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 13] = [
+        Operation::New,
+        Operation::FromIter,
+        Operation::PushBack,
+        Operation::PushFront,
+        Operation::PopBack,
+        Operation::PopFront,
+        Operation::Insert,
+        Operation::InsertSeq,
+        Operation::Get,
+        Operation::GetMiss,
+        Operation::Remove,
+        Operation::Range,
+        Operation::Iter,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::New => "new",
+            Operation::FromIter => "from-iter",
+            Operation::PushBack => "push-back",
+            Operation::PushFront => "push-front",
+            Operation::PopBack => "pop-back",
+            Operation::PopFront => "pop-front",
+            Operation::Insert => "insert",
+            Operation::InsertSeq => "insert-seq",
+            Operation::Get => "get",
+            Operation::GetMiss => "get-miss",
+            Operation::Remove => "remove",
+            Operation::Range => "range",
+            Operation::Iter => "iter",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Operation> {
+        Operation::ALL
+            .into_iter()
+            .find(|operation| operation.name() == name)
+    }
+
+    pub fn kinds(self) -> &'static [Kind] {
+        match self {
+            Operation::New => &[Kind::Sequence, Kind::OrderedMap, Kind::HashMap],
+            // `AVLTree` has no `FromIterator`, so on the maps this would only
+            // repeat `insert`.
+            Operation::FromIter
+            | Operation::PushBack
+            | Operation::PushFront
+            | Operation::PopBack
+            | Operation::PopFront => &[Kind::Sequence],
+            Operation::Insert
+            | Operation::InsertSeq
+            | Operation::Get
+            | Operation::GetMiss
+            | Operation::Remove => &[Kind::OrderedMap, Kind::HashMap],
+            Operation::Range => &[Kind::OrderedMap],
+            Operation::Iter => &[Kind::Sequence, Kind::OrderedMap, Kind::HashMap],
+        }
+    }
+
+    pub fn applies_to(self, structure: Structure) -> bool {
+        self.kinds().contains(&structure.kind())
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Operation::New => "create an empty container",
+            Operation::FromIter => "collect `size` values into a new container",
+            Operation::PushBack => "push `size` values onto the back",
+            Operation::PushFront => "push `size` values onto the front",
+            Operation::PopBack => "pop `size` values off the back",
+            Operation::PopFront => "pop `size` values off the front",
+            Operation::Insert => "insert `size` random keys",
+            Operation::InsertSeq => "insert the keys 0..`size` in ascending order",
+            Operation::Get => "look up `size` present keys",
+            Operation::GetMiss => "look up `size` absent keys",
+            Operation::Remove => "remove `size` present keys in random order",
+            Operation::Range => "scan the middle half of the keys in order",
+            Operation::Iter => "visit every entry",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub fn run(
+    structure: Structure,
+    operation: Operation,
+    data: &[u64],
+    config: &Config,
+) -> Measurement {
+    match structure {
+        Structure::LinkedList => run_sequence::<LinkedList<u64>>(operation, data, config),
+        Structure::UnrolledList => run_sequence::<UnrolledList<u64>>(operation, data, config),
+        Structure::StdLinkedList => {
+            run_sequence::<collections::LinkedList<u64>>(operation, data, config)
+        }
+        Structure::VecDeque => run_sequence::<VecDeque<u64>>(operation, data, config),
+        Structure::AVLTree => run_map::<u64, AVLTree<u64, u64>>(operation, data, config),
+        Structure::BTreeMap => run_map::<u64, BTreeMap<u64, u64>>(operation, data, config),
+        Structure::HashMap => run_map::<u64, HashMap<u64, u64>>(operation, data, config),
+        Structure::AVLTreeString => {
+            run_map::<String, AVLTree<String, u64>>(operation, data, config)
+        }
+        Structure::BTreeMapString => {
+            run_map::<String, BTreeMap<String, u64>>(operation, data, config)
+        }
+        Structure::HashMapString => {
+            run_map::<String, HashMap<String, u64>>(operation, data, config)
+        }
+    }
+}
+
+// Traces hold u64 values, so map traces only run on the u64 ordered maps.
+pub fn can_replay(structure: Structure, trace: &Trace<u64>) -> bool {
+    match structure.kind() {
+        Kind::Sequence => trace.is_deque_trace(),
+        Kind::OrderedMap => {
+            trace.is_map_trace() && matches!(structure, Structure::AVLTree | Structure::BTreeMap)
+        }
+        Kind::HashMap => false,
+    }
+}
+
+// Checks the structure against the model once, then times the replay alone.
+pub fn replay(
+    structure: Structure,
+    trace: &Trace<u64>,
+    config: &Config,
+) -> Result<Measurement, ReplayError<u64>> {
+    match structure {
+        Structure::LinkedList => replay_sequence::<LinkedList<u64>>(trace, config),
+        Structure::UnrolledList => replay_sequence::<UnrolledList<u64>>(trace, config),
+        Structure::StdLinkedList => replay_sequence::<collections::LinkedList<u64>>(trace, config),
+        Structure::VecDeque => replay_sequence::<VecDeque<u64>>(trace, config),
+        Structure::AVLTree => replay_map::<AVLTree<u64, u64>>(trace, config),
+        Structure::BTreeMap => replay_map::<BTreeMap<u64, u64>>(trace, config),
+        _ => unreachable!("{structure} cannot replay a trace"),
+    }
+}
+
+fn replay_sequence<D>(trace: &Trace<u64>, config: &Config) -> Result<Measurement, ReplayError<u64>>
+where
+    D: Deque<u64> + Default,
+{
+    trace::replay_deque(trace, &mut D::default())?;
+    Ok(bench(config, trace.len(), D::default, |mut deque| {
+        let outcomes = trace.apply_deque(&mut deque);
+        (deque, outcomes)
+    }))
+}
+
+fn replay_map<M>(trace: &Trace<u64>, config: &Config) -> Result<Measurement, ReplayError<u64>>
+where
+    M: OrderedMap<u64, u64> + Default,
+{
+    trace::replay_map(trace, &mut M::default())?;
+    Ok(bench(config, trace.len(), M::default, |mut map| {
+        let outcomes = trace.apply_map(&mut map);
+        (map, outcomes)
+    }))
+}
+
+fn run_sequence<D>(operation: Operation, data: &[u64], config: &Config) -> Measurement
+where
+    D: Sequence,
+{
+    let size = data.len();
+    let filled = || data.iter().copied().collect::<D>();
+
+    match operation {
+        Operation::New => bench(config, 1, || (), |_| D::default()),
+        Operation::FromIter => bench(config, size, || (), |_| filled()),
+        Operation::PushBack => bench(config, size, D::default, |mut deque| {
+            for &value in data {
+                deque.push_back(value);
+            }
+            deque
+        }),
+        Operation::PushFront => bench(config, size, D::default, |mut deque| {
+            for &value in data {
+                deque.push_front(value);
+            }
+            deque
+        }),
+        Operation::PopBack => bench(config, size, filled, |mut deque| {
+            for _ in data {
+                deque.pop_back();
+            }
+            deque
+        }),
+        Operation::PopFront => bench(config, size, filled, |mut deque| {
+            for _ in data {
+                deque.pop_front();
+            }
+            deque
+        }),
+        Operation::Iter => bench(config, size, filled, |deque| {
+            let sum = deque.walk();
+            (deque, sum)
+        }),
+        Operation::Insert
+        | Operation::InsertSeq
+        | Operation::Get
+        | Operation::GetMiss
+        | Operation::Remove
+        | Operation::Range => unreachable!("{operation} does not apply to sequences"),
+    }
+}
+
+// `Deque` only reaches the ends, so walking the whole sequence needs each
+// type's own iterator.
+trait Sequence: Deque<u64> + Default + FromIterator<u64> {
+    // Sums the values so the walk cannot be optimised away.
+    fn walk(&self) -> u64;
+}
+
+impl Sequence for LinkedList<u64> {
+    fn walk(&self) -> u64 {
+        self.iter().fold(0, u64::wrapping_add)
+    }
+}
+
+impl Sequence for UnrolledList<u64> {
+    fn walk(&self) -> u64 {
+        self.iter().fold(0, u64::wrapping_add)
+    }
+}
+
+impl Sequence for collections::LinkedList<u64> {
+    fn walk(&self) -> u64 {
+        self.iter().copied().fold(0, u64::wrapping_add)
+    }
+}
+
+impl Sequence for VecDeque<u64> {
+    fn walk(&self) -> u64 {
+        self.iter().copied().fold(0, u64::wrapping_add)
+    }
+}
+
+// Keys are derived from the seeded random vector so every map type sees the
+// same keys in the same order.
+trait Key: Ord + Hash + Clone {
+    fn from_u64(value: u64) -> Self;
+    // A key that is not among those made by `from_u64`. For u64 the complement
+    // of a random key only collides with another one with odds of 2^-64.
+    fn missing(value: u64) -> Self;
+}
+
+impl Key for u64 {
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+
+    fn missing(value: u64) -> Self {
+        !value
+    }
+}
+
+impl Key for String {
+    fn from_u64(value: u64) -> Self {
+        format!("{value:016x}")
+    }
+
+    fn missing(value: u64) -> Self {
+        format!("{value:016x}~")
+    }
+}
+
+// The operations every map under test supports. `OrderedMap` cannot be used
+// directly because `HashMap` has no order to implement it with.
+trait Map<K>: Default {
+    fn insert(&mut self, key: K, value: u64);
+    fn contains(&self, key: &K) -> bool;
+    fn remove(&mut self, key: &K) -> bool;
+    // Both sum the values they visit, since counting alone lets `HashMap`
+    // answer from its length without touching any entry.
+    fn walk(&self) -> u64;
+    fn scan(&self, from: &K, to: &K) -> u64;
+}
+
+impl<K> Map<K> for AVLTree<K, u64>
+where
+    K: Ord + Clone,
+{
+    fn insert(&mut self, key: K, value: u64) {
+        OrderedMap::insert(self, key, value);
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        AVLTree::remove(self, key.clone()).is_ok()
+    }
+
+    fn walk(&self) -> u64 {
+        self.iter().map(|(_, value)| value).sum()
+    }
+
+    fn scan(&self, from: &K, to: &K) -> u64 {
+        self.range(from.clone()..to.clone())
+            .map(|(_, value)| value)
+            .sum()
+    }
+}
+
+impl<K> Map<K> for BTreeMap<K, u64>
+where
+    K: Ord,
+{
+    fn insert(&mut self, key: K, value: u64) {
+        BTreeMap::insert(self, key, value);
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        BTreeMap::remove(self, key).is_some()
+    }
+
+    fn walk(&self) -> u64 {
+        self.values().sum()
+    }
+
+    fn scan(&self, from: &K, to: &K) -> u64 {
+        self.range(from..to).map(|(_, value)| value).sum()
+    }
+}
+
+impl<K> Map<K> for HashMap<K, u64>
+where
+    K: Hash + Eq,
+{
+    fn insert(&mut self, key: K, value: u64) {
+        HashMap::insert(self, key, value);
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    fn remove(&mut self, key: &K) -> bool {
+        HashMap::remove(self, key).is_some()
+    }
+
+    fn walk(&self) -> u64 {
+        self.values().sum()
+    }
+
+    fn scan(&self, _from: &K, _to: &K) -> u64 {
+        unreachable!("hash maps have no key order to scan")
+    }
+}
+
+fn run_map<K, M>(operation: Operation, data: &[u64], config: &Config) -> Measurement
+where
+    K: Key,
+    M: Map<K>,
+{
+    let size = data.len();
+    let keys: Vec<K> = data.iter().copied().map(K::from_u64).collect();
+    let filled = || {
+        let mut map = M::default();
+        for (value, key) in keys.iter().enumerate() {
+            map.insert(key.clone(), value as u64);
+        }
+        map
+    };
+    // Owned keys are handed over by the setup so cloning them stays off the
+    // clock and out of the allocation counts. Their buffer is still live at
+    // the peak, so `without_key_buffer` takes it back out.
+    let insert_all = |keys: &[K]| {
+        let keys = keys.to_vec();
+        move || (M::default(), keys.clone())
+    };
+    let insert = |(mut map, keys): (M, Vec<K>)| {
+        for (value, key) in keys.into_iter().enumerate() {
+            map.insert(key, value as u64);
+        }
+        map
+    };
+
+    match operation {
+        Operation::New => bench(config, 1, || (), |_| M::default()),
+        Operation::Insert => {
+            without_key_buffer::<K>(bench(config, size, insert_all(&keys), insert), size)
+        }
+        Operation::InsertSeq => {
+            let sequential: Vec<K> = (0..size as u64).map(K::from_u64).collect();
+            without_key_buffer::<K>(bench(config, size, insert_all(&sequential), insert), size)
+        }
+        Operation::Get => bench(config, size, filled, |map| {
+            let found = keys.iter().filter(|key| map.contains(key)).count();
+            (map, found)
+        }),
+        Operation::GetMiss => {
+            let missing: Vec<K> = data.iter().copied().map(K::missing).collect();
+            bench(config, size, filled, |map| {
+                let found = missing.iter().filter(|key| map.contains(key)).count();
+                (map, found)
+            })
+        }
+        Operation::Remove => bench(config, size, filled, |mut map| {
+            let removed = keys.iter().filter(|key| map.remove(key)).count();
+            (map, removed)
+        }),
+        Operation::Range => {
+            let mut sorted = keys.clone();
+            sorted.sort();
+            let (from, to) = match sorted.len() {
+                0 => return bench(config, size, filled, |map| (map, 0)),
+                len => (sorted[len / 4].clone(), sorted[len * 3 / 4].clone()),
+            };
+            bench(config, size, filled, |map| {
+                let sum = map.scan(&from, &to);
+                (map, sum)
+            })
+        }
+        Operation::Iter => bench(config, size, filled, |map| {
+            let sum = map.walk();
+            (map, sum)
+        }),
+        Operation::FromIter
+        | Operation::PushBack
+        | Operation::PushFront
+        | Operation::PopBack
+        | Operation::PopFront => {
+            unreachable!("{operation} does not apply to maps")
+        }
+    }
+}
+
+// The peak is taken from before setup, so for the insert workloads it also
+// counts the `Vec` of keys being consumed. `Vec::clone` allocates exactly
+// `size` keys, and the buffer is only freed once every key is inserted.
+fn without_key_buffer<K>(mut measurement: Measurement, size: usize) -> Measurement {
+    let memory = &mut measurement.memory;
+    memory.peak_bytes = memory.peak_bytes.saturating_sub(size * mem::size_of::<K>());
+    memory.bytes_per_element = memory.peak_bytes as f64 / size.max(1) as f64;
+    measurement
+}
+
+#[cfg(test)]
+mod workload_tests {
+    use std::time::Duration;
+
+    use super::{run, Operation, Structure};
+    use crate::harness::Config;
+
+    #[test]
+    fn test_names_and_kinds() {
+        for structure in Structure::ALL {
+            assert_eq!(Structure::from_name(structure.name()), Some(structure));
+        }
+        for operation in Operation::ALL {
+            assert_eq!(Operation::from_name(operation.name()), Some(operation));
+        }
+
+        assert!(Operation::Range.applies_to(Structure::AVLTreeString));
+        assert!(!Operation::Range.applies_to(Structure::HashMap));
+        assert!(Operation::Iter.applies_to(Structure::HashMapString));
+        assert!(Operation::Iter.applies_to(Structure::UnrolledList));
+        assert!(!Operation::Get.applies_to(Structure::VecDeque));
+        assert!(!Operation::PushBack.applies_to(Structure::BTreeMap));
+        assert!(Operation::FromIter.applies_to(Structure::LinkedList));
+        assert!(!Operation::FromIter.applies_to(Structure::AVLTree));
+    }
+
+    #[test]
+    fn test_insert_reports_container_memory() {
+        let config = Config {
+            warmup: 1,
+            samples: 1,
+            sample_time: Duration::ZERO,
+            ..Config::default()
+        };
+        let data: Vec<u64> = (0..1000).map(|n| n * 7919 % 1000).collect();
+
+        for structure in [Structure::AVLTree, Structure::BTreeMapString] {
+            let insert = run(structure, Operation::Insert, &data, &config).memory;
+            let get = run(structure, Operation::Get, &data, &config).memory;
+            assert_eq!(insert.bytes_per_element, get.bytes_per_element);
+        }
+    }
+}
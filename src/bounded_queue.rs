@@ -0,0 +1,351 @@
+use std::{
+    fmt,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::linked_list::LinkedList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushError<T> {
+    Full(T),
+    Closed(T),
+    Timeout(T),
+}
+
+impl<T> PushError<T> {
+    // Hands back the item that could not be queued.
+    pub fn into_inner(self) -> T {
+        match self {
+            PushError::Full(item) | PushError::Closed(item) | PushError::Timeout(item) => item,
+        }
+    }
+}
+
+impl<T> fmt::Display for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushError::Full(_) => write!(f, "the queue is full"),
+            PushError::Closed(_) => write!(f, "the queue is closed"),
+            PushError::Timeout(_) => write!(f, "timed out waiting for space in the queue"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for PushError<T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopError {
+    Empty,
+    // Only once the queue is closed and every queued item has been taken.
+    Closed,
+    Timeout,
+}
+
+impl fmt::Display for PopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PopError::Empty => write!(f, "the queue is empty"),
+            PopError::Closed => write!(f, "the queue is closed"),
+            PopError::Timeout => write!(f, "timed out waiting for an item"),
+        }
+    }
+}
+
+impl std::error::Error for PopError {}
+
+// `LinkedList` is not `Send` because its nodes are `Rc`s. Every `Rc` here is
+// owned by the list itself, none is ever handed out, and the list is only
+// touched with the mutex held, so moving it between threads is sound.
+struct SendList<T>(LinkedList<T>)
+where
+    T: Copy + PartialEq;
+
+unsafe impl<T> Send for SendList<T> where T: Copy + PartialEq + Send {}
+
+struct State<T>
+where
+    T: Copy + PartialEq,
+{
+    list: SendList<T>,
+    closed: bool,
+}
+
+// A fixed-capacity FIFO for handing items between threads. Any number of
+// producers and consumers can share it through an `Arc`.
+pub struct BoundedQueue<T>
+where
+    T: Copy + PartialEq,
+{
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+impl<T> BoundedQueue<T>
+where
+    T: Copy + PartialEq + Send,
+{
+    // Panics if `capacity` is zero, since nothing could ever be pushed.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a bounded queue needs a capacity of at least 1"
+        );
+
+        BoundedQueue {
+            state: Mutex::new(State {
+                list: SendList(LinkedList::new()),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().list.0.length()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    // Blocks while the queue is full.
+    pub fn push(&self, item: T) -> Result<(), PushError<T>> {
+        self.push_until(item, None)
+    }
+
+    pub fn try_push(&self, item: T) -> Result<(), PushError<T>> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(PushError::Closed(item));
+        }
+        if state.list.0.length() >= self.capacity {
+            return Err(PushError::Full(item));
+        }
+
+        self.enqueue(&mut state, item);
+        Ok(())
+    }
+
+    pub fn push_timeout(&self, item: T, timeout: Duration) -> Result<(), PushError<T>> {
+        self.push_until(item, Some(Instant::now() + timeout))
+    }
+
+    // Blocks while the queue is empty. Items queued before `close` are still
+    // handed out.
+    pub fn pop(&self) -> Result<T, PopError> {
+        self.pop_until(None)
+    }
+
+    pub fn try_pop(&self) -> Result<T, PopError> {
+        let mut state = self.lock();
+        match self.dequeue(&mut state) {
+            Some(item) => Ok(item),
+            None if state.closed => Err(PopError::Closed),
+            None => Err(PopError::Empty),
+        }
+    }
+
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<T, PopError> {
+        self.pop_until(Some(Instant::now() + timeout))
+    }
+
+    // Refuses further pushes and wakes every blocked thread. Consumers can
+    // still drain what is left.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    fn push_until(&self, item: T, deadline: Option<Instant>) -> Result<(), PushError<T>> {
+        let mut state = self.lock();
+        loop {
+            if state.closed {
+                return Err(PushError::Closed(item));
+            }
+            if state.list.0.length() < self.capacity {
+                self.enqueue(&mut state, item);
+                return Ok(());
+            }
+
+            state = match Self::wait(&self.not_full, state, deadline) {
+                Some(state) => state,
+                None => return Err(PushError::Timeout(item)),
+            };
+        }
+    }
+
+    fn pop_until(&self, deadline: Option<Instant>) -> Result<T, PopError> {
+        let mut state = self.lock();
+        loop {
+            if let Some(item) = self.dequeue(&mut state) {
+                return Ok(item);
+            }
+            if state.closed {
+                return Err(PopError::Closed);
+            }
+
+            state = match Self::wait(&self.not_empty, state, deadline) {
+                Some(state) => state,
+                None => return Err(PopError::Timeout),
+            };
+        }
+    }
+
+    fn enqueue(&self, state: &mut State<T>, item: T) {
+        state.list.0.push_back(item);
+        self.not_empty.notify_one();
+    }
+
+    fn dequeue(&self, state: &mut State<T>) -> Option<T> {
+        let item = state.list.0.pop_front().ok()?;
+        self.not_full.notify_one();
+        Some(item)
+    }
+
+    // Returns `None` once the deadline has passed.
+    fn wait<'a>(
+        condvar: &Condvar,
+        state: MutexGuard<'a, State<T>>,
+        deadline: Option<Instant>,
+    ) -> Option<MutexGuard<'a, State<T>>> {
+        let Some(deadline) = deadline else {
+            return Some(condvar.wait(state).unwrap_or_else(PoisonError::into_inner));
+        };
+
+        let timeout = deadline.checked_duration_since(Instant::now())?;
+        let (state, _) = condvar
+            .wait_timeout(state, timeout)
+            .unwrap_or_else(PoisonError::into_inner);
+        Some(state)
+    }
+
+    // Items are `Copy`, so a panic on another thread cannot leave the list
+    // half-updated and a poisoned lock is safe to keep using.
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod bounded_queue_tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use super::{BoundedQueue, PopError, PushError};
+
+    #[test]
+    fn test_try_push_and_pop() {
+        let queue = BoundedQueue::new(2);
+        assert_eq!(queue.try_pop(), Err(PopError::Empty));
+
+        queue.try_push(1).unwrap();
+        queue.try_push(2).unwrap();
+        assert_eq!(queue.try_push(3), Err(PushError::Full(3)));
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.try_pop(), Ok(1));
+        queue.close();
+        assert_eq!(queue.try_push(4).map_err(PushError::into_inner), Err(4));
+        assert_eq!(queue.try_pop(), Ok(2));
+        assert_eq!(queue.try_pop(), Err(PopError::Closed));
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let queue = BoundedQueue::new(1);
+        let wait = Duration::from_millis(20);
+
+        assert_eq!(queue.pop_timeout(wait), Err(PopError::Timeout));
+        queue.push_timeout('a', wait).unwrap();
+        assert_eq!(queue.push_timeout('b', wait), Err(PushError::Timeout('b')));
+        assert_eq!(queue.pop_timeout(wait), Ok('a'));
+    }
+
+    #[test]
+    fn test_close_wakes_waiters() {
+        let queue = Arc::new(BoundedQueue::<u32>::new(1));
+        let consumers: Vec<_> = (0..3)
+            .map(|_| {
+                let queue = queue.clone();
+                thread::spawn(move || queue.pop())
+            })
+            .collect();
+
+        let full = Arc::new(BoundedQueue::new(1));
+        full.push(0).unwrap();
+        let producer = {
+            let full = full.clone();
+            thread::spawn(move || full.push(1))
+        };
+
+        thread::sleep(Duration::from_millis(20));
+        queue.close();
+        full.close();
+
+        for consumer in consumers {
+            assert_eq!(consumer.join().unwrap(), Err(PopError::Closed));
+        }
+        assert_eq!(producer.join().unwrap(), Err(PushError::Closed(1)));
+    }
+
+    #[test]
+    fn test_many_producers_and_consumers() {
+        const PRODUCERS: u64 = 4;
+        const CONSUMERS: usize = 4;
+        const ITEMS: u64 = 10_000;
+
+        let queue = Arc::new(BoundedQueue::new(16));
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    for item in 0..ITEMS {
+                        queue.push(producer * ITEMS + item).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    let mut seen = Vec::new();
+                    while let Ok(item) = queue.pop() {
+                        assert!(queue.len() <= queue.capacity());
+                        seen.push(item);
+                    }
+                    seen
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        queue.close();
+
+        let mut seen: Vec<u64> = consumers
+            .into_iter()
+            .flat_map(|consumer| consumer.join().unwrap())
+            .collect();
+        seen.sort_unstable();
+
+        // Every item arrives exactly once.
+        assert_eq!(seen, (0..PRODUCERS * ITEMS).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,389 @@
+use std::{cell::RefCell, fmt, iter::FromIterator, marker::PhantomData, rc::Rc};
+
+use crate::linked_list::LinkedListError;
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+struct Node<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    value: T,
+    next: Link<T>,
+}
+
+// A ring where the last node links back to the first. `current` is the
+// movable cursor and `prev` the node just behind it, which makes removing
+// the current node O(1). With one node both point at it and it links to
+// itself.
+pub struct CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    current: Link<T>,
+    prev: Link<T>,
+    length: usize,
+}
+
+impl<T> CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    pub fn new() -> Self {
+        CircularList {
+            current: None,
+            prev: None,
+            length: 0,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn current(&self) -> Result<T, LinkedListError> {
+        let current = self.current.as_ref().ok_or(LinkedListError::EmptyList)?;
+        let value = current.borrow().value;
+        Ok(value)
+    }
+
+    pub fn contains(&self, item: T) -> bool {
+        self.iter().any(|value| value == item)
+    }
+
+    // Moves the cursor `n` nodes forward, wrapping around the ring.
+    pub fn advance(&mut self, n: usize) {
+        if self.length == 0 {
+            return;
+        }
+
+        for _ in 0..n % self.length {
+            let next = self
+                .current
+                .as_ref()
+                .and_then(|node| node.borrow().next.clone());
+            self.prev = std::mem::replace(&mut self.current, next);
+        }
+    }
+
+    // Like `advance`, but a negative `k` moves the cursor backwards.
+    pub fn rotate(&mut self, k: isize) {
+        if self.length == 0 {
+            return;
+        }
+
+        let steps = k.rem_euclid(self.length as isize) as usize;
+        self.advance(steps);
+    }
+
+    // The new node comes up next; the cursor stays where it is.
+    pub fn insert_after_current(&mut self, item: T) {
+        let Some(current) = self.current.clone() else {
+            self.insert_first(item);
+            return;
+        };
+
+        let next = current.borrow_mut().next.take();
+        let node = Rc::new(RefCell::new(Node { value: item, next }));
+        current.borrow_mut().next = Some(node.clone());
+
+        if self.length == 1 {
+            self.prev = Some(node);
+        }
+        self.length += 1;
+    }
+
+    // Inserts just behind the cursor, so the new node is the last one reached
+    // going round from `current`.
+    pub fn push_back(&mut self, item: T) {
+        let Some(prev) = self.prev.clone() else {
+            self.insert_first(item);
+            return;
+        };
+
+        let node = Rc::new(RefCell::new(Node {
+            value: item,
+            next: self.current.clone(),
+        }));
+        prev.borrow_mut().next = Some(node.clone());
+        self.prev = Some(node);
+        self.length += 1;
+    }
+
+    // The node after the removed one becomes current.
+    pub fn remove_current(&mut self) -> Result<T, LinkedListError> {
+        let current = self.current.take().ok_or(LinkedListError::EmptyList)?;
+        let next = current.borrow_mut().next.take();
+        self.length -= 1;
+
+        if self.length == 0 {
+            self.prev = None;
+        } else {
+            if let Some(prev) = &self.prev {
+                prev.borrow_mut().next = next.clone();
+            }
+            self.current = next;
+        }
+
+        let value = current.borrow().value;
+        Ok(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.prev = None;
+        self.length = 0;
+
+        // Taking each `next` breaks the cycle; the loop ends when it comes
+        // back round to the first node, whose link is already gone.
+        let mut node = self.current.take();
+        while let Some(temp) = node {
+            node = temp.borrow_mut().next.take();
+        }
+    }
+
+    // Goes round exactly once, starting at the cursor.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.current.clone(),
+            remaining: self.length,
+            marker: PhantomData,
+        }
+    }
+
+    // Goes round exactly once, starting `offset` nodes after the cursor.
+    pub fn iter_from(&self, offset: usize) -> Result<Iter<'_, T>, LinkedListError> {
+        if offset >= self.length {
+            return Err(LinkedListError::OutOfBounds {
+                index: offset,
+                len: self.length,
+            });
+        }
+
+        let mut iter = self.iter();
+        for _ in 0..offset {
+            iter.next();
+        }
+        iter.remaining = self.length;
+        Ok(iter)
+    }
+
+    // Josephus elimination: counting from the cursor, removes every `step`th
+    // node until the ring is empty, yielding values in the order they go.
+    // Panics if `step` is zero.
+    pub fn eliminate(&mut self, step: usize) -> Eliminate<'_, T> {
+        assert!(step > 0, "the elimination step must be at least 1");
+        Eliminate { list: self, step }
+    }
+
+    fn insert_first(&mut self, item: T) {
+        let node = Rc::new(RefCell::new(Node {
+            value: item,
+            next: None,
+        }));
+        node.borrow_mut().next = Some(node.clone());
+        self.current = Some(node.clone());
+        self.prev = Some(node);
+        self.length = 1;
+    }
+}
+
+impl<T> Drop for CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+pub struct Iter<'a, T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    node: Link<T>,
+    remaining: usize,
+    marker: PhantomData<&'a CircularList<T>>,
+}
+
+impl<T> Iterator for Iter<'_, T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.node.take()?;
+        let node = node.borrow();
+        self.node = node.next.clone();
+        self.remaining -= 1;
+        Some(node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> where T: Copy + std::cmp::PartialEq {}
+
+impl<'a, T> IntoIterator for &'a CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+pub struct Eliminate<'a, T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    list: &'a mut CircularList<T>,
+    step: usize,
+}
+
+impl<T> Iterator for Eliminate<'_, T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.advance(self.step - 1);
+        self.list.remove_current().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.length, Some(self.list.length))
+    }
+}
+
+// Keeps the iteration order, with the cursor on the first item.
+impl<T> FromIterator<T> for CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = CircularList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> Default for CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn default() -> Self {
+        CircularList::new()
+    }
+}
+
+impl<T> fmt::Debug for CircularList<T>
+where
+    T: Copy + std::cmp::PartialEq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod circular_list_tests {
+    use std::{cell::RefCell, rc::Weak};
+
+    use super::{CircularList, Node};
+    use crate::linked_list::LinkedListError;
+
+    #[test]
+    fn test_cursor_movement() {
+        let mut ring: CircularList<_> = (1..=5).collect();
+        assert_eq!(ring.current(), Ok(1));
+
+        ring.advance(2);
+        assert_eq!(ring.iter().collect::<Vec<_>>(), [3, 4, 5, 1, 2]);
+        ring.advance(7);
+        assert_eq!(ring.current(), Ok(5));
+        ring.rotate(-3);
+        assert_eq!(ring.current(), Ok(2));
+        ring.rotate(4);
+        assert_eq!(ring.current(), Ok(1));
+
+        assert_eq!(
+            ring.iter_from(3).unwrap().collect::<Vec<_>>(),
+            [4, 5, 1, 2, 3]
+        );
+        assert!(matches!(
+            ring.iter_from(5),
+            Err(LinkedListError::OutOfBounds { index: 5, len: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut ring = CircularList::new();
+        assert_eq!(ring.remove_current(), Err(LinkedListError::EmptyList));
+        ring.advance(3);
+
+        ring.insert_after_current('a');
+        ring.insert_after_current('c');
+        ring.insert_after_current('b');
+        ring.push_back('d');
+        assert_eq!(ring.iter().collect::<String>(), "abcd");
+
+        assert_eq!(ring.remove_current(), Ok('a'));
+        assert_eq!(ring.current(), Ok('b'));
+        ring.advance(2);
+        assert_eq!(ring.remove_current(), Ok('d'));
+        assert_eq!(ring.iter().collect::<String>(), "bc");
+        assert_eq!(ring.remove_current(), Ok('b'));
+        assert_eq!(ring.remove_current(), Ok('c'));
+        assert!(ring.is_empty());
+
+        ring.push_back('e');
+        ring.push_back('f');
+        assert_eq!(format!("{ring:?}"), "['e', 'f']");
+        assert!(ring.contains('f'));
+    }
+
+    #[test]
+    fn test_josephus() {
+        let mut ring: CircularList<_> = (1..=7).collect();
+        let order: Vec<_> = ring.eliminate(3).collect();
+        assert_eq!(order, [3, 6, 2, 7, 5, 1, 4]);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_drop_breaks_the_cycle() {
+        let ring: CircularList<_> = (0..100_000).collect();
+        let node: Weak<RefCell<Node<i32>>> =
+            ring.current.as_ref().map(std::rc::Rc::downgrade).unwrap();
+
+        drop(ring);
+        assert!(node.upgrade().is_none());
+    }
+}
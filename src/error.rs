@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::{avl_tree::AVLTreeError, linked_list::LinkedListError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error<K = (), V = ()> {
+    LinkedList(LinkedListError),
+    AVLTree(AVLTreeError<K, V>),
+}
+
+impl<K, V> fmt::Display for Error<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LinkedList(err) => write!(f, "linked list error: {err}"),
+            Error::AVLTree(err) => write!(f, "avl tree error: {err}"),
+        }
+    }
+}
+
+impl<K, V> std::error::Error for Error<K, V>
+where
+    K: fmt::Debug + 'static,
+    V: fmt::Debug + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::LinkedList(err) => Some(err),
+            Error::AVLTree(err) => Some(err),
+        }
+    }
+}
+
+impl<K, V> From<LinkedListError> for Error<K, V> {
+    fn from(value: LinkedListError) -> Self {
+        Error::LinkedList(value)
+    }
+}
+
+impl<K, V> From<AVLTreeError<K, V>> for Error<K, V> {
+    fn from(value: AVLTreeError<K, V>) -> Self {
+        Error::AVLTree(value)
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use std::error::Error as _;
+
+    use super::Error;
+    use crate::{
+        avl_tree::AVLTreeError,
+        linked_list::{LinkedList, LinkedListError},
+    };
+
+    fn front_if_small(list: &LinkedList<u32>) -> Result<u32, Error<u32, u32>> {
+        let value = list.front()?;
+        if value > 10 {
+            Err(AVLTreeError::NodeNotFound)?;
+        }
+        Ok(value)
+    }
+
+    #[test]
+    fn test_question_mark() {
+        let mut list = LinkedList::new();
+
+        assert_eq!(
+            front_if_small(&list),
+            Err(Error::LinkedList(LinkedListError::EmptyList))
+        );
+
+        list.push_back(70);
+        assert_eq!(
+            front_if_small(&list),
+            Err(Error::AVLTree(AVLTreeError::NodeNotFound))
+        );
+
+        list.push_front(7);
+        assert_eq!(front_if_small(&list), Ok(7));
+    }
+
+    #[test]
+    fn test_display_and_source() {
+        let err: Error = LinkedListError::OutOfBounds { index: 3, len: 1 }.into();
+
+        assert_eq!(
+            err.to_string(),
+            "linked list error: index 3 is out of bounds for a list of length 1"
+        );
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            "index 3 is out of bounds for a list of length 1"
+        );
+    }
+}
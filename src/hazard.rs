@@ -0,0 +1,267 @@
+use std::{
+    ptr,
+    sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+// Retired nodes are only scanned once this many more than there are hazard
+// slots have piled up, so each scan frees at least half of what it looks at.
+const SCAN_SLACK: usize = 64;
+
+struct Slot {
+    hazard: AtomicPtr<()>,
+    active: AtomicBool,
+    next: *mut Slot,
+}
+
+struct Retired {
+    pointer: *mut (),
+    delete: unsafe fn(*mut ()),
+    next: *mut Retired,
+}
+
+// Hazard pointers for the lock-free structures. A thread publishes the node
+// it is about to dereference in a slot; a removed node is retired instead of
+// freed and only deleted once no slot points at it. Every structure owns its
+// own domain, so whatever is still retired is freed when the structure drops.
+pub(crate) struct Domain {
+    slots: AtomicPtr<Slot>,
+    slot_count: AtomicUsize,
+    retired: AtomicPtr<Retired>,
+    retired_count: AtomicUsize,
+}
+
+impl Domain {
+    pub(crate) fn new() -> Self {
+        Domain {
+            slots: AtomicPtr::new(ptr::null_mut()),
+            slot_count: AtomicUsize::new(0),
+            retired: AtomicPtr::new(ptr::null_mut()),
+            retired_count: AtomicUsize::new(0),
+        }
+    }
+
+    // Claims a free slot, adding one when every slot is in use. Slots are never
+    // unlinked, so walking the list needs no protection of its own.
+    pub(crate) fn guard(&self) -> Guard<'_> {
+        let mut slot = self.slots.load(Ordering::Acquire);
+        while !slot.is_null() {
+            let current = unsafe { &*slot };
+            if current
+                .active
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Guard { slot: current };
+            }
+            slot = current.next;
+        }
+
+        let slot = Box::into_raw(Box::new(Slot {
+            hazard: AtomicPtr::new(ptr::null_mut()),
+            active: AtomicBool::new(true),
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.slots.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*slot).next = head };
+            match self
+                .slots
+                .compare_exchange_weak(head, slot, Ordering::AcqRel, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+        self.slot_count.fetch_add(1, Ordering::Relaxed);
+
+        Guard {
+            slot: unsafe { &*slot },
+        }
+    }
+
+    // Safety: `pointer` must come from `Box::<T>::into_raw`, must already be
+    // unreachable from the structure, and must not be retired twice.
+    pub(crate) unsafe fn retire<T>(&self, pointer: *mut T) {
+        unsafe fn delete<T>(pointer: *mut ()) {
+            drop(Box::from_raw(pointer as *mut T));
+        }
+
+        let retired = Box::into_raw(Box::new(Retired {
+            pointer: pointer as *mut (),
+            delete: delete::<T>,
+            next: ptr::null_mut(),
+        }));
+        // Counted before it is published, so a concurrent scan that frees it
+        // can never take the count below zero.
+        let count = self.retired_count.fetch_add(1, Ordering::Relaxed) + 1;
+        self.push_retired(retired, retired);
+
+        if count >= 2 * self.slot_count.load(Ordering::Relaxed) + SCAN_SLACK {
+            self.scan();
+        }
+    }
+
+    // Frees every retired node no slot is protecting. Concurrent scans each
+    // take a disjoint part of the retired list. The list is taken before the
+    // hazards are read: a node retired after the read could still be guarded
+    // by a hazard published before it.
+    fn scan(&self) {
+        let mut retired = self.retired.swap(ptr::null_mut(), Ordering::AcqRel);
+        fence(Ordering::SeqCst);
+
+        let mut hazards = Vec::new();
+        let mut slot = self.slots.load(Ordering::Acquire);
+        while !slot.is_null() {
+            let current = unsafe { &*slot };
+            let hazard = current.hazard.load(Ordering::SeqCst);
+            if !hazard.is_null() {
+                hazards.push(hazard);
+            }
+            slot = current.next;
+        }
+        hazards.sort_unstable();
+
+        let mut kept: (*mut Retired, *mut Retired) = (ptr::null_mut(), ptr::null_mut());
+        let mut freed = 0;
+        while !retired.is_null() {
+            let next = unsafe { (*retired).next };
+            let pointer = unsafe { (*retired).pointer };
+
+            if hazards.binary_search(&pointer).is_ok() {
+                unsafe { (*retired).next = kept.0 };
+                if kept.1.is_null() {
+                    kept.1 = retired;
+                }
+                kept.0 = retired;
+            } else {
+                unsafe {
+                    let retired = Box::from_raw(retired);
+                    (retired.delete)(retired.pointer);
+                }
+                freed += 1;
+            }
+            retired = next;
+        }
+
+        self.retired_count.fetch_sub(freed, Ordering::Relaxed);
+        if !kept.0.is_null() {
+            self.push_retired(kept.0, kept.1);
+        }
+    }
+
+    // Splices the chain `first..=last` onto the retired list.
+    fn push_retired(&self, first: *mut Retired, last: *mut Retired) {
+        let mut head = self.retired.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*last).next = head };
+            match self.retired.compare_exchange_weak(
+                head,
+                first,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+}
+
+impl Drop for Domain {
+    fn drop(&mut self) {
+        let mut retired = *self.retired.get_mut();
+        while !retired.is_null() {
+            let current = unsafe { Box::from_raw(retired) };
+            unsafe { (current.delete)(current.pointer) };
+            retired = current.next;
+        }
+
+        let mut slot = *self.slots.get_mut();
+        while !slot.is_null() {
+            let current = unsafe { Box::from_raw(slot) };
+            slot = current.next;
+        }
+    }
+}
+
+// One claimed slot. Dropping it clears the hazard and frees the slot for the
+// next guard.
+pub(crate) struct Guard<'a> {
+    slot: &'a Slot,
+}
+
+impl Guard<'_> {
+    // Loads `source` and keeps the node it points at from being freed until
+    // the guard protects something else or is dropped.
+    pub(crate) fn protect<T>(&self, source: &AtomicPtr<T>) -> *mut T {
+        let mut pointer = source.load(Ordering::Acquire);
+        loop {
+            self.set(pointer);
+            let current = source.load(Ordering::Acquire);
+            if current == pointer {
+                return pointer;
+            }
+            pointer = current;
+        }
+    }
+
+    // Publishes `pointer` without checking it is still reachable; the caller
+    // has to validate that afterwards.
+    pub(crate) fn set<T>(&self, pointer: *mut T) {
+        self.slot.hazard.store(pointer as *mut (), Ordering::SeqCst);
+        fence(Ordering::SeqCst);
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.slot.hazard.store(ptr::null_mut(), Ordering::Release);
+        self.slot.active.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod hazard_tests {
+    use std::sync::{
+        atomic::{AtomicPtr, AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::Domain;
+
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_protected_nodes_survive_a_scan() {
+        let kept_dropped = Arc::new(AtomicUsize::new(0));
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let domain = Domain::new();
+
+        let kept = Box::into_raw(Box::new(Counted(kept_dropped.clone())));
+        let source = AtomicPtr::new(kept);
+        let guard = domain.guard();
+        assert_eq!(guard.protect(&source), kept);
+
+        unsafe { domain.retire(kept) };
+        for _ in 0..200 {
+            unsafe { domain.retire(Box::into_raw(Box::new(Counted(dropped.clone())))) };
+        }
+        assert!(dropped.load(Ordering::Relaxed) > 0);
+        assert_eq!(kept_dropped.load(Ordering::Relaxed), 0);
+
+        // Guards hand their slot back, so the next one reuses it.
+        drop(guard);
+        drop(domain.guard());
+        assert_eq!(domain.slot_count.load(Ordering::Relaxed), 1);
+
+        drop(domain);
+        assert_eq!(dropped.load(Ordering::Relaxed), 200);
+        assert_eq!(kept_dropped.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,546 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    avl_tree::AVLTree,
+    clock::{Clock, SystemClock},
+};
+
+type EntryRef<K, V> = Rc<RefCell<Entry<K, V>>>;
+type BucketRef<K, V> = Rc<RefCell<Bucket<K, V>>>;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    bucket: Weak<RefCell<Bucket<K, V>>>,
+    prev: Option<Weak<RefCell<Entry<K, V>>>>,
+    next: Option<EntryRef<K, V>>,
+}
+
+// All entries used the same number of times, most recently used at `head`.
+// Buckets form their own list in ascending frequency and are dropped as soon
+// as they empty, so moving an entry up one use is O(1).
+struct Bucket<K, V> {
+    frequency: u64,
+    head: Option<EntryRef<K, V>>,
+    tail: Option<EntryRef<K, V>>,
+    prev: Option<Weak<RefCell<Bucket<K, V>>>>,
+    next: Option<BucketRef<K, V>>,
+}
+
+pub struct LfuCache<K, V, C = SystemClock>
+where
+    K: Ord + Clone,
+    C: Clock,
+{
+    index: AVLTree<K, EntryRef<K, V>>,
+    // The least used bucket, whose tail is the next entry to evict.
+    buckets: Option<BucketRef<K, V>>,
+    capacity: usize,
+    clock: C,
+    // The aging period and when counts were last halved.
+    decay: Option<(Duration, Instant)>,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K, V> LfuCache<K, V>
+where
+    K: Ord + Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self::with_clock(capacity, SystemClock)
+    }
+}
+
+impl<K, V, C> LfuCache<K, V, C>
+where
+    K: Ord + Clone,
+    C: Clock,
+{
+    pub fn with_clock(capacity: usize, clock: C) -> Self {
+        LfuCache {
+            index: AVLTree::new(),
+            buckets: None,
+            capacity,
+            clock,
+            decay: None,
+            on_evict: None,
+        }
+    }
+
+    // Halves every use count once per `period`, so entries that were popular
+    // long ago do not stay cached forever. Aging runs on the next `get` or
+    // `put` after a period has passed. `None` turns it off.
+    pub fn set_decay(&mut self, period: Option<Duration>) {
+        self.decay = period
+            .filter(|period| !period.is_zero())
+            .map(|period| (period, self.clock.now()));
+    }
+
+    // Called with every entry pushed out by `put` or `resize`, but not with
+    // entries taken out by `pop_lfu`, `remove` or `clear`.
+    pub fn on_evict<F>(&mut self, callback: F)
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    pub fn frequency(&self, key: &K) -> Option<u64> {
+        let entry = self.index.get(key.clone()).ok()?;
+        let bucket = entry.borrow().bucket.upgrade()?;
+        let frequency = bucket.borrow().frequency;
+        Some(frequency)
+    }
+
+    // Counts as a use of the entry.
+    pub fn get(&mut self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.age();
+        let entry = self.index.get(key.clone()).ok()?;
+        self.touch(&entry);
+
+        let value = entry.borrow().value.clone();
+        Some(value)
+    }
+
+    // Like `get`, but does not count as a use.
+    pub fn peek(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let entry = self.index.get(key.clone()).ok()?;
+        let value = entry.borrow().value.clone();
+        Some(value)
+    }
+
+    // Overwriting counts as a use and returns the old value. A new key at
+    // capacity evicts the least frequently used entry first, breaking ties by
+    // recency.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        self.age();
+
+        if let Ok(entry) = self.index.get(key.clone()) {
+            self.touch(&entry);
+            return Some(std::mem::replace(&mut entry.borrow_mut().value, value));
+        }
+
+        if self.capacity == 0 {
+            self.evict(key, value);
+            return None;
+        }
+
+        while self.len() >= self.capacity {
+            let Some((key, value)) = self.pop_lfu() else {
+                break;
+            };
+            self.evict(key, value);
+        }
+
+        let bucket = match &self.buckets {
+            Some(bucket) if bucket.borrow().frequency == 1 => bucket.clone(),
+            _ => self.insert_bucket_after(None, 1),
+        };
+        let entry = Rc::new(RefCell::new(Entry {
+            key: key.clone(),
+            value,
+            bucket: Weak::new(),
+            prev: None,
+            next: None,
+        }));
+        Self::push_entry(&bucket, entry.clone());
+        let _ = self.index.insert(key, entry);
+
+        None
+    }
+
+    pub fn pop_lfu(&mut self) -> Option<(K, V)> {
+        let bucket = self.buckets.clone()?;
+        let entry = bucket.borrow().tail.clone()?;
+        let key = entry.borrow().key.clone();
+        Some(self.unlink(&key, entry))
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.index.get(key.clone()).ok()?;
+        Some(self.unlink(key, entry).1)
+    }
+
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.len() > capacity {
+            let Some((key, value)) = self.pop_lfu() else {
+                break;
+            };
+            self.evict(key, value);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.index.clear();
+
+        let mut bucket = self.buckets.take();
+        while let Some(current) = bucket {
+            let mut current = current.borrow_mut();
+            bucket = current.next.take();
+            current.tail = None;
+
+            let mut entry = current.head.take();
+            while let Some(temp) = entry {
+                entry = temp.borrow_mut().next.take();
+            }
+        }
+    }
+
+    fn evict(&mut self, key: K, value: V) {
+        if let Some(callback) = self.on_evict.as_mut() {
+            callback(key, value);
+        }
+    }
+
+    fn unlink(&mut self, key: &K, entry: EntryRef<K, V>) -> (K, V) {
+        self.detach_entry(&entry);
+        let _ = self.index.remove(key.clone());
+
+        let Ok(entry) = Rc::try_unwrap(entry) else {
+            unreachable!("an unlinked entry is only owned by the caller");
+        };
+        let entry = entry.into_inner();
+        (entry.key, entry.value)
+    }
+
+    fn touch(&mut self, entry: &EntryRef<K, V>) {
+        let Some(bucket) = entry.borrow().bucket.upgrade() else {
+            unreachable!("a cached entry always sits in a bucket");
+        };
+        let Some(frequency) = bucket.borrow().frequency.checked_add(1) else {
+            // The count is saturated, so there is no bucket above this one;
+            // the entry only moves up to most recently used within it. It is
+            // not the head, so detaching it cannot empty the bucket.
+            if !bucket
+                .borrow()
+                .head
+                .as_ref()
+                .is_some_and(|head| Rc::ptr_eq(head, entry))
+            {
+                self.detach_entry(entry);
+                Self::push_entry(&bucket, entry.clone());
+            }
+            return;
+        };
+        let next = bucket.borrow().next.clone();
+
+        // Make the target bucket before leaving this one, which may empty and
+        // be removed.
+        let target = match next {
+            Some(next) if next.borrow().frequency == frequency => next,
+            _ => self.insert_bucket_after(Some(&bucket), frequency),
+        };
+        self.detach_entry(entry);
+        Self::push_entry(&target, entry.clone());
+    }
+
+    fn insert_bucket_after(
+        &mut self,
+        after: Option<&BucketRef<K, V>>,
+        frequency: u64,
+    ) -> BucketRef<K, V> {
+        let bucket = Rc::new(RefCell::new(Bucket {
+            frequency,
+            head: None,
+            tail: None,
+            prev: after.map(Rc::downgrade),
+            next: None,
+        }));
+
+        let next = match after {
+            Some(after) => after.borrow_mut().next.replace(bucket.clone()),
+            None => self.buckets.replace(bucket.clone()),
+        };
+        if let Some(next) = &next {
+            next.borrow_mut().prev = Some(Rc::downgrade(&bucket));
+        }
+        bucket.borrow_mut().next = next;
+
+        bucket
+    }
+
+    fn remove_bucket(&mut self, bucket: &BucketRef<K, V>) {
+        let (prev, next) = {
+            let mut bucket = bucket.borrow_mut();
+            let prev = bucket.prev.take().and_then(|prev| prev.upgrade());
+            (prev, bucket.next.take())
+        };
+
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.clone(),
+            None => self.buckets = next.clone(),
+        }
+        if let Some(next) = next {
+            next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade);
+        }
+    }
+
+    fn detach_entry(&mut self, entry: &EntryRef<K, V>) {
+        let (bucket, prev, next) = {
+            let mut entry = entry.borrow_mut();
+            let prev = entry.prev.take().and_then(|prev| prev.upgrade());
+            (entry.bucket.upgrade(), prev, entry.next.take())
+        };
+        let Some(bucket) = bucket else {
+            return;
+        };
+
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.clone(),
+            None => bucket.borrow_mut().head = next.clone(),
+        }
+        match next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => bucket.borrow_mut().tail = prev,
+        }
+
+        if bucket.borrow().head.is_none() {
+            self.remove_bucket(&bucket);
+        }
+    }
+
+    fn push_entry(bucket: &BucketRef<K, V>, entry: EntryRef<K, V>) {
+        let mut bucket_mut = bucket.borrow_mut();
+        {
+            let mut entry_mut = entry.borrow_mut();
+            entry_mut.bucket = Rc::downgrade(bucket);
+            entry_mut.prev = None;
+            entry_mut.next = bucket_mut.head.take();
+        }
+
+        match &entry.borrow().next {
+            Some(next) => next.borrow_mut().prev = Some(Rc::downgrade(&entry)),
+            None => bucket_mut.tail = Some(entry.clone()),
+        }
+        bucket_mut.head = Some(entry);
+    }
+
+    // Halving keeps the buckets in order, so the list is rebuilt in one pass,
+    // merging buckets whose counts collapse to the same value.
+    fn age(&mut self) {
+        let Some((period, last)) = self.decay else {
+            return;
+        };
+        let now = self.clock.now();
+        let periods = now.duration_since(last).as_nanos() / period.as_nanos();
+        let periods = u32::try_from(periods).unwrap_or(u32::MAX);
+        if periods == 0 {
+            return;
+        }
+        self.decay = Some((period, last + period * periods));
+
+        let mut bucket = self.buckets.take();
+        let mut last: Option<BucketRef<K, V>> = None;
+
+        while let Some(current) = bucket {
+            bucket = current.borrow_mut().next.take();
+            // Shifting by 64 or more halves every count down to the floor.
+            let frequency = current
+                .borrow()
+                .frequency
+                .checked_shr(periods)
+                .unwrap_or(0)
+                .max(1);
+
+            match &last {
+                Some(merged) if merged.borrow().frequency == frequency => {
+                    // The entries had the higher count, so they go in ahead
+                    // of the ones already there.
+                    let mut entries = Vec::new();
+                    let mut entry = current.borrow_mut().head.take();
+                    while let Some(temp) = entry {
+                        entry = temp.borrow_mut().next.take();
+                        entries.push(temp);
+                    }
+                    current.borrow_mut().tail = None;
+
+                    for entry in entries.into_iter().rev() {
+                        Self::push_entry(merged, entry);
+                    }
+                }
+                _ => {
+                    {
+                        let mut current = current.borrow_mut();
+                        current.frequency = frequency;
+                        current.prev = last.as_ref().map(Rc::downgrade);
+                    }
+                    match &last {
+                        Some(last) => last.borrow_mut().next = Some(current.clone()),
+                        None => self.buckets = Some(current.clone()),
+                    }
+                    last = Some(current);
+                }
+            }
+        }
+    }
+}
+
+impl<K, V, C> Drop for LfuCache<K, V, C>
+where
+    K: Ord + Clone,
+    C: Clock,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod lfu_cache_tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use super::LfuCache;
+    use crate::clock::ManualClock;
+
+    #[test]
+    fn test_evicts_least_frequently_used() {
+        let mut cache = LfuCache::new(3);
+        cache.put('a', 1);
+        cache.put('b', 2);
+        cache.put('c', 3);
+
+        cache.get(&'a');
+        cache.get(&'a');
+        cache.get(&'b');
+        assert_eq!(cache.frequency(&'a'), Some(3));
+        assert_eq!(cache.frequency(&'c'), Some(1));
+
+        cache.put('d', 4);
+        assert!(!cache.contains(&'c'));
+
+        // 'd' has one use, 'b' two and 'a' three.
+        assert_eq!(cache.pop_lfu(), Some(('d', 4)));
+        assert_eq!(cache.pop_lfu(), Some(('b', 2)));
+        assert_eq!(cache.pop_lfu(), Some(('a', 1)));
+        assert_eq!(cache.pop_lfu(), None);
+    }
+
+    #[test]
+    fn test_ties_break_by_recency() {
+        let mut cache = LfuCache::new(2);
+        cache.put(1, 'x');
+        cache.put(2, 'y');
+        assert_eq!(cache.put(1, 'X'), Some('x'));
+        cache.get(&2);
+
+        // Both were used twice; 1 was used longest ago.
+        cache.put(3, 'z');
+        assert_eq!(cache.peek(&1), None);
+        assert_eq!(cache.peek(&2), Some('y'));
+        assert_eq!(cache.frequency(&2), Some(2));
+    }
+
+    #[test]
+    fn test_remove_resize_and_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let mut cache = LfuCache::new(4);
+        let sink = evicted.clone();
+        cache.on_evict(move |key, value| sink.borrow_mut().push((key, value)));
+
+        for key in 0..4 {
+            cache.put(key, key * 10);
+            for _ in 0..key {
+                cache.get(&key);
+            }
+        }
+        assert_eq!(cache.remove(&2), Some(20));
+        assert_eq!(cache.remove(&2), None);
+
+        cache.resize(1);
+        assert_eq!(*evicted.borrow(), [(0, 0), (1, 10)]);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.peek(&3), Some(30));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_decay() {
+        let clock = ManualClock::new();
+        let mut cache = LfuCache::with_clock(2, clock.clone());
+        cache.set_decay(Some(Duration::from_secs(10)));
+
+        cache.put("old", 1);
+        for _ in 0..7 {
+            cache.get(&"old");
+        }
+        cache.put("new", 2);
+        cache.get(&"new");
+        assert_eq!(cache.frequency(&"old"), Some(8));
+
+        // Three halvings bring both counts down to 1, so the next use of
+        // "new" is enough to outrank "old".
+        clock.advance(Duration::from_secs(35));
+        cache.get(&"new");
+        assert_eq!(cache.frequency(&"old"), Some(1));
+        assert_eq!(cache.frequency(&"new"), Some(2));
+
+        cache.put("newest", 3);
+        assert!(!cache.contains(&"old"));
+    }
+
+    #[test]
+    fn test_saturated_counts() {
+        let clock = ManualClock::new();
+        let mut cache = LfuCache::with_clock(3, clock.clone());
+        cache.put('a', 1);
+        cache.put('b', 2);
+        if let Some(bucket) = &cache.buckets {
+            bucket.borrow_mut().frequency = u64::MAX;
+        }
+
+        // Uses at the ceiling keep one bucket and only reorder by recency.
+        cache.get(&'a');
+        cache.get(&'b');
+        cache.get(&'a');
+        assert_eq!(cache.frequency(&'a'), Some(u64::MAX));
+        assert_eq!(cache.frequency(&'b'), Some(u64::MAX));
+        assert!(cache.buckets.as_ref().unwrap().borrow().next.is_none());
+
+        cache.put('c', 3);
+        assert_eq!(cache.pop_lfu(), Some(('c', 3)));
+        assert_eq!(cache.pop_lfu(), Some(('b', 2)));
+
+        // More periods than fit in a `u32` still decay all the way down.
+        cache.set_decay(Some(Duration::from_nanos(1)));
+        clock.advance(Duration::from_nanos((1 << 32) + 1));
+        cache.get(&'a');
+        assert_eq!(cache.frequency(&'a'), Some(2));
+    }
+
+    #[test]
+    fn test_drop_large_cache() {
+        let mut cache = LfuCache::new(200_000);
+        for key in 0..200_000 {
+            cache.put(key, key);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(feature = "avl")]
+pub mod avl_tree;
+#[cfg(feature = "linked-list")]
+pub mod bounded_queue;
+#[cfg(feature = "linked-list")]
+pub mod circular_list;
+#[cfg(feature = "cache")]
+pub mod clock;
+#[cfg(all(feature = "linked-list", feature = "avl"))]
+pub mod error;
+#[cfg(feature = "lock-free")]
+mod hazard;
+#[cfg(feature = "cache")]
+pub mod lfu_cache;
+#[cfg(feature = "linked-list")]
+pub mod linked_list;
+#[cfg(feature = "list-arena")]
+pub mod list_arena;
+#[cfg(feature = "cache")]
+pub mod lru_cache;
+#[cfg(all(test, feature = "linked-list", feature = "avl"))]
+mod model_tests;
+#[cfg(feature = "lock-free")]
+pub mod ms_queue;
+#[cfg(feature = "persistent-list")]
+pub mod persistent_list;
+#[cfg(feature = "linked-list")]
+pub mod queue;
+#[cfg(feature = "skip-list")]
+pub mod skip_list;
+#[cfg(feature = "linked-list")]
+pub mod stack;
+pub mod trace;
+pub mod traits;
+#[cfg(feature = "lock-free")]
+pub mod treiber_stack;
+#[cfg(feature = "cache")]
+pub mod ttl_cache;
+#[cfg(feature = "linked-list")]
+pub mod unrolled_list;
+
+#[cfg(feature = "avl")]
+pub use avl_tree::{AVLTree, AVLTreeError};
+#[cfg(feature = "linked-list")]
+pub use bounded_queue::BoundedQueue;
+#[cfg(feature = "linked-list")]
+pub use circular_list::CircularList;
+#[cfg(feature = "cache")]
+pub use clock::{Clock, ManualClock, SystemClock};
+#[cfg(all(feature = "linked-list", feature = "avl"))]
+pub use error::Error;
+#[cfg(feature = "cache")]
+pub use lfu_cache::LfuCache;
+#[cfg(feature = "linked-list")]
+pub use linked_list::{LinkedList, LinkedListError};
+#[cfg(feature = "list-arena")]
+pub use list_arena::{ArenaError, ListArena, ListId, NodeId};
+#[cfg(feature = "cache")]
+pub use lru_cache::LruCache;
+#[cfg(feature = "lock-free")]
+pub use ms_queue::MsQueue;
+#[cfg(feature = "persistent-list")]
+pub use persistent_list::PersistentList;
+#[cfg(feature = "linked-list")]
+pub use queue::Queue;
+#[cfg(feature = "skip-list")]
+pub use skip_list::{IndexedSkipList, SkipList, SkipListError};
+#[cfg(feature = "linked-list")]
+pub use stack::Stack;
+pub use trace::{Recorder, Trace};
+pub use traits::{Collection, Deque, OrderedMap};
+#[cfg(feature = "lock-free")]
+pub use treiber_stack::TreiberStack;
+#[cfg(feature = "cache")]
+pub use ttl_cache::TtlCache;
+#[cfg(feature = "linked-list")]
+pub use unrolled_list::UnrolledList;
+
+pub mod prelude {
+    #[cfg(feature = "avl")]
+    pub use crate::avl_tree::{AVLTree, AVLTreeError};
+    #[cfg(feature = "linked-list")]
+    pub use crate::bounded_queue::BoundedQueue;
+    #[cfg(feature = "linked-list")]
+    pub use crate::circular_list::CircularList;
+    #[cfg(feature = "cache")]
+    pub use crate::clock::{Clock, ManualClock, SystemClock};
+    #[cfg(all(feature = "linked-list", feature = "avl"))]
+    pub use crate::error::Error;
+    #[cfg(feature = "cache")]
+    pub use crate::lfu_cache::LfuCache;
+    #[cfg(feature = "linked-list")]
+    pub use crate::linked_list::{LinkedList, LinkedListError};
+    #[cfg(feature = "list-arena")]
+    pub use crate::list_arena::{ArenaError, ListArena, ListId, NodeId};
+    #[cfg(feature = "cache")]
+    pub use crate::lru_cache::LruCache;
+    #[cfg(feature = "lock-free")]
+    pub use crate::ms_queue::MsQueue;
+    #[cfg(feature = "persistent-list")]
+    pub use crate::persistent_list::PersistentList;
+    #[cfg(feature = "linked-list")]
+    pub use crate::queue::Queue;
+    #[cfg(feature = "skip-list")]
+    pub use crate::skip_list::{IndexedSkipList, SkipList, SkipListError};
+    #[cfg(feature = "linked-list")]
+    pub use crate::stack::Stack;
+    pub use crate::trace::{Recorder, Trace};
+    pub use crate::traits::{Collection, Deque, OrderedMap};
+    #[cfg(feature = "lock-free")]
+    pub use crate::treiber_stack::TreiberStack;
+    #[cfg(feature = "cache")]
+    pub use crate::ttl_cache::TtlCache;
+    #[cfg(feature = "linked-list")]
+    pub use crate::unrolled_list::UnrolledList;
+}
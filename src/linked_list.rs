@@ -1,12 +1,40 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    rc::Rc,
+};
 type Link<T> = Option<Rc<RefCell<Node<T>>>>;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LinkedListError {
-    OutOfBounds,
+    OutOfBounds { index: usize, len: usize },
     EmptyList,
+    // Raised by the capacity-limited `Stack` and `Queue`.
+    Full { capacity: usize },
 }
 
+impl fmt::Display for LinkedListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkedListError::OutOfBounds { index, len } => {
+                write!(
+                    f,
+                    "index {index} is out of bounds for a list of length {len}"
+                )
+            }
+            LinkedListError::EmptyList => write!(f, "the list is empty"),
+            LinkedListError::Full { capacity } => {
+                write!(f, "the list is full at its capacity of {capacity}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LinkedListError {}
+
 struct Node<T>
 where
     T: Copy + std::cmp::PartialEq,
@@ -53,31 +81,32 @@ where
         self.length == 0
     }
 
-    pub fn append(&mut self, other: LinkedList<T>) {
+    pub fn append(&mut self, mut other: LinkedList<T>) {
+        if other.head.is_none() {
+            return;
+        }
+
         self.length += other.length;
 
-        if self.length == 0 {
-            self.head = other.head;
-            self.tail = other.tail;
+        if self.head.is_none() {
+            self.head = other.head.take();
+            self.tail = other.tail.take();
             return;
         }
 
         if let Some(tail) = self.tail.clone() {
-            let mut tail = (*tail).borrow_mut();
-            (*tail).next = other.head;
-            self.tail = other.tail;
+            let mut tail = tail.borrow_mut();
+            tail.next = other.head.take();
+            self.tail = other.tail.take();
         }
     }
 
     pub fn clear(&mut self) {
-        let mut node = self.head.clone();
-        self.head = None;
+        let mut node = self.head.take();
         self.tail = None;
 
         while let Some(temp) = node {
-            node = (*temp).borrow().next.clone();
-            let mut temp = (*temp).borrow_mut();
-            (*temp).next = None;
+            node = temp.borrow_mut().next.take();
         }
 
         self.length = 0;
@@ -105,7 +134,10 @@ where
         }
 
         if idx + 1 > self.length {
-            return Err(LinkedListError::OutOfBounds);
+            return Err(LinkedListError::OutOfBounds {
+                index: idx,
+                len: self.length,
+            });
         }
 
         return Ok(self.get_node(idx).borrow().value);
@@ -117,7 +149,10 @@ where
         }
 
         if idx > self.length - 1 {
-            return Err(LinkedListError::OutOfBounds);
+            return Err(LinkedListError::OutOfBounds {
+                index: idx,
+                len: self.length,
+            });
         }
 
         self.length -= 1;
@@ -126,6 +161,9 @@ where
             let node = self.head.clone().unwrap();
             let node = (*node).borrow();
             self.head = node.next.clone();
+            if self.length == 0 {
+                self.tail = None;
+            }
             return Ok(node.value);
         }
 
@@ -140,11 +178,103 @@ where
         let value = next.borrow().value;
         let next = next.borrow().next.clone();
         let mut node = (*node).borrow_mut();
-        (*node).next = next;
+        node.next = next;
 
         Ok(value)
     }
 
+    pub fn insert(&mut self, idx: usize, item: T) -> Result<(), LinkedListError> {
+        if idx > self.length {
+            return Err(LinkedListError::OutOfBounds {
+                index: idx,
+                len: self.length,
+            });
+        }
+
+        if idx == 0 {
+            self.push_front(item);
+            return Ok(());
+        }
+
+        if idx == self.length {
+            self.push_back(item);
+            return Ok(());
+        }
+
+        let prev = self.get_node(idx - 1);
+        let mut prev = prev.borrow_mut();
+        let node = Rc::new(RefCell::new(Node {
+            value: item,
+            next: prev.next.take(),
+        }));
+        prev.next = Some(node);
+        self.length += 1;
+
+        Ok(())
+    }
+
+    pub fn split_off(&mut self, idx: usize) -> Result<LinkedList<T>, LinkedListError> {
+        if idx > self.length {
+            return Err(LinkedListError::OutOfBounds {
+                index: idx,
+                len: self.length,
+            });
+        }
+
+        if idx == 0 {
+            return Ok(std::mem::take(self));
+        }
+
+        if idx == self.length {
+            return Ok(LinkedList::new());
+        }
+
+        let prev = self.get_node(idx - 1);
+        let other = LinkedList {
+            head: prev.borrow_mut().next.take(),
+            tail: self.tail.take(),
+            length: self.length - idx,
+        };
+
+        self.tail = Some(prev);
+        self.length = idx;
+
+        Ok(other)
+    }
+
+    pub fn splice(&mut self, idx: usize, mut other: LinkedList<T>) -> Result<(), LinkedListError> {
+        if idx > self.length {
+            return Err(LinkedListError::OutOfBounds {
+                index: idx,
+                len: self.length,
+            });
+        }
+
+        if idx == self.length {
+            self.append(other);
+            return Ok(());
+        }
+
+        let (Some(head), Some(tail)) = (other.head.take(), other.tail.take()) else {
+            return Ok(());
+        };
+
+        if idx == 0 {
+            tail.borrow_mut().next = self.head.take();
+            self.head = Some(head);
+        } else {
+            let prev = self.get_node(idx - 1);
+            let mut prev = prev.borrow_mut();
+            tail.borrow_mut().next = prev.next.take();
+            prev.next = Some(head);
+        }
+
+        self.length += other.length;
+        other.length = 0;
+
+        Ok(())
+    }
+
     pub fn front(&self) -> Result<T, LinkedListError> {
         if self.head.is_none() || self.length == 0 {
             return Err(LinkedListError::EmptyList);
@@ -191,7 +321,7 @@ where
         let node = self.get_node(self.length - 1);
         let temp = node.clone();
         let mut node = (*node).borrow_mut();
-        (*node).next = None;
+        node.next = None;
         self.tail = Some(temp);
 
         Ok(value)
@@ -224,12 +354,231 @@ where
 
         if let Some(tail) = self.tail.clone() {
             let mut tail = (*tail).borrow_mut();
-            (*tail).next = Some(node.clone());
+            tail.next = Some(node.clone());
         }
 
         self.tail = Some(node);
     }
 
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    pub fn sort_unstable_by<F>(&mut self, cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_by(cmp);
+    }
+
+    // Bottom-up merge sort: each pass merges neighbouring runs of `width`
+    // nodes, so no recursion and no allocation, only relinking.
+    pub fn sort_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.length < 2 {
+            return;
+        }
+
+        let mut head = self.head.take();
+        self.tail = None;
+
+        let mut width = 1;
+        while width < self.length {
+            let mut rest = head;
+            let mut new_head: Link<T> = None;
+            let mut new_tail: Link<T> = None;
+
+            while let Some(left) = rest {
+                let right = Self::cut_after(&left, width);
+                rest = match &right {
+                    Some(right) => Self::cut_after(right, width),
+                    None => None,
+                };
+
+                let (run_head, run_tail) = Self::merge(Some(left), right, &mut cmp);
+                match &new_tail {
+                    Some(tail) => tail.borrow_mut().next = run_head,
+                    None => new_head = run_head,
+                }
+                new_tail = run_tail;
+            }
+
+            head = new_head;
+            self.tail = new_tail;
+            width *= 2;
+        }
+
+        self.head = head;
+    }
+
+    pub fn merge_sorted(&mut self, mut other: LinkedList<T>)
+    where
+        T: Ord,
+    {
+        let (head, tail) = Self::merge(self.head.take(), other.head.take(), &mut |a, b| a.cmp(b));
+
+        self.head = head;
+        self.tail = tail;
+        self.length += other.length;
+
+        other.tail = None;
+        other.length = 0;
+    }
+
+    pub fn reverse(&mut self) {
+        let mut prev: Link<T> = None;
+        let mut node = self.head.take();
+        self.tail = node.clone();
+
+        while let Some(current) = node {
+            node = current.borrow_mut().next.take();
+            current.borrow_mut().next = prev;
+            prev = Some(current);
+        }
+
+        self.head = prev;
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|value| f(value));
+    }
+
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut prev: Link<T> = None;
+        let mut node = self.head.clone();
+
+        while let Some(current) = node {
+            let keep = f(&mut current.borrow_mut().value);
+            node = current.borrow().next.clone();
+
+            if keep {
+                prev = Some(current);
+            } else {
+                self.unlink_after(&prev, node.clone());
+            }
+        }
+
+        self.tail = prev;
+    }
+
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // `same_bucket` gets the candidate first and the last kept element
+    // second, the same argument order as `Vec::dedup_by`.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let Some(mut prev) = self.head.clone() else {
+            return;
+        };
+
+        let mut node = prev.borrow().next.clone();
+        while let Some(current) = node {
+            let duplicate = same_bucket(
+                &mut current.borrow_mut().value,
+                &mut prev.borrow_mut().value,
+            );
+            node = current.borrow().next.clone();
+
+            if duplicate {
+                self.unlink_after(&Some(prev.clone()), node.clone());
+            } else {
+                prev = current;
+            }
+        }
+
+        self.tail = Some(prev);
+    }
+
+    pub fn rotate_left(&mut self, k: usize) {
+        if self.length == 0 {
+            return;
+        }
+
+        let k = k % self.length;
+        if k == 0 {
+            return;
+        }
+
+        let new_tail = self.get_node(k - 1);
+        let new_head = new_tail.borrow_mut().next.take();
+
+        if let Some(tail) = &self.tail {
+            tail.borrow_mut().next = self.head.take();
+        }
+
+        self.head = new_head;
+        self.tail = Some(new_tail);
+    }
+
+    pub fn rotate_right(&mut self, k: usize) {
+        if self.length == 0 {
+            return;
+        }
+
+        self.rotate_left(self.length - k % self.length);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.head.clone(),
+            remaining: self.length,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            node: self.head.clone(),
+            prev: None,
+            list: self,
+            pred,
+        }
+    }
+
+    // Points `prev` (or the head when `prev` is `None`) at `next`, dropping
+    // the node in between.
+    fn unlink_after(&mut self, prev: &Link<T>, next: Link<T>) {
+        match prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => self.head = next,
+        }
+        self.length -= 1;
+    }
+
     #[inline]
     fn get_node(&self, idx: usize) -> Rc<RefCell<Node<T>>> {
         let mut node = self.head.clone();
@@ -242,7 +591,122 @@ where
             place += 1;
         }
 
-        return node.unwrap().clone();
+        node.unwrap().clone()
+    }
+
+    // Detaches and returns everything after the first `count` nodes.
+    fn cut_after(node: &Rc<RefCell<Node<T>>>, count: usize) -> Link<T> {
+        let mut node = node.clone();
+        for _ in 1..count {
+            let next = node.borrow().next.clone();
+            match next {
+                Some(next) => node = next,
+                None => return None,
+            }
+        }
+
+        let rest = node.borrow_mut().next.take();
+        rest
+    }
+
+    // Merges two sorted chains, taking from `left` on ties to keep the sort
+    // stable. Returns the head and tail of the merged chain.
+    fn merge<F>(mut left: Link<T>, mut right: Link<T>, cmp: &mut F) -> (Link<T>, Link<T>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut head: Link<T> = None;
+        let mut tail: Link<T> = None;
+
+        loop {
+            let node = match (left.take(), right.take()) {
+                (Some(l), Some(r)) => {
+                    if cmp(&r.borrow().value, &l.borrow().value) == Ordering::Less {
+                        right = r.borrow_mut().next.take();
+                        left = Some(l);
+                        r
+                    } else {
+                        left = l.borrow_mut().next.take();
+                        right = Some(r);
+                        l
+                    }
+                }
+                (Some(rest), None) | (None, Some(rest)) => {
+                    match &tail {
+                        Some(tail) => tail.borrow_mut().next = Some(rest.clone()),
+                        None => head = Some(rest.clone()),
+                    }
+
+                    let mut last = rest;
+                    loop {
+                        let next = last.borrow().next.clone();
+                        match next {
+                            Some(next) => last = next,
+                            None => break,
+                        }
+                    }
+
+                    return (head, Some(last));
+                }
+                (None, None) => return (head, tail),
+            };
+
+            match &tail {
+                Some(tail) => tail.borrow_mut().next = Some(node.clone()),
+                None => head = Some(node.clone()),
+            }
+            tail = Some(node);
+        }
+    }
+}
+
+// Dropping the nodes one at a time keeps the drop glue from recursing down
+// the whole chain, which overflows the stack on long lists.
+impl<T> Drop for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+pub struct ExtractIf<'a, T, F>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    list: &'a mut LinkedList<T>,
+    prev: Link<T>,
+    node: Link<T>,
+    pred: F,
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    T: Copy + std::cmp::PartialEq,
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while let Some(current) = self.node.take() {
+            let extract = (self.pred)(&mut current.borrow_mut().value);
+            self.node = current.borrow().next.clone();
+
+            if extract {
+                self.list.unlink_after(&self.prev, self.node.clone());
+                if self.node.is_none() {
+                    self.list.tail = self.prev.clone();
+                }
+
+                let value = current.borrow().value;
+                return Some(value);
+            }
+
+            self.prev = Some(current);
+        }
+
+        None
     }
 }
 
@@ -259,8 +723,207 @@ where
     }
 }
 
+impl<T> From<Vec<T>> for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn from(value: Vec<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<T> From<LinkedList<T>> for Vec<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn from(value: LinkedList<T>) -> Self {
+        let mut vec = Vec::with_capacity(value.length);
+        vec.extend(value.iter());
+        vec
+    }
+}
+
+pub struct Iter<'a, T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    node: Link<T>,
+    remaining: usize,
+    marker: PhantomData<&'a LinkedList<T>>,
+}
+
+impl<T> Iterator for Iter<'_, T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.node.take()?;
+        let node = node.borrow();
+        self.node = node.next.clone();
+        self.remaining -= 1;
+        Some(node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> where T: Copy + std::cmp::PartialEq {}
+
+pub struct IntoIter<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    list: LinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.length, Some(self.list.length))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> where T: Copy + std::cmp::PartialEq {}
+
+impl<T> IntoIterator for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for LinkedList<T>
+where
+    T: 'a + Copy + std::cmp::PartialEq,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> Default for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+impl<T> Clone for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn clone(&self) -> Self {
+        self.iter().collect()
+    }
+}
+
+impl<T> fmt::Debug for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> PartialEq for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for LinkedList<T> where T: Copy + Eq {}
+
+impl<T> PartialOrd for LinkedList<T>
+where
+    T: Copy + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T> Ord for LinkedList<T>
+where
+    T: Copy + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> Hash for LinkedList<T>
+where
+    T: Copy + std::cmp::PartialEq + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.length);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
 #[cfg(test)]
 mod linked_list_tests {
+    use std::hash::{Hash, Hasher};
+
     use crate::linked_list::LinkedListError;
 
     use super::LinkedList;
@@ -269,17 +932,17 @@ mod linked_list_tests {
     fn test_empty() {
         let mut list: LinkedList<i32> = LinkedList::new();
 
-        assert_eq!(list.is_empty(), true);
+        assert!(list.is_empty());
 
         list.push_front(9);
 
-        assert_eq!(list.is_empty(), false);
+        assert!(!list.is_empty());
 
         let mut list: LinkedList<i32> = LinkedList::new();
 
         list.push_back(9);
 
-        assert_eq!(list.is_empty(), false);
+        assert!(!list.is_empty());
     }
 
     #[test]
@@ -311,7 +974,10 @@ mod linked_list_tests {
         assert_eq!(list.get(2).unwrap(), 100);
         assert_eq!(list.get(0).unwrap(), 10);
 
-        assert_eq!(list.get(4), Err(LinkedListError::OutOfBounds));
+        assert_eq!(
+            list.get(4),
+            Err(LinkedListError::OutOfBounds { index: 4, len: 3 })
+        );
     }
 
     #[test]
@@ -435,7 +1101,10 @@ mod linked_list_tests {
         list.push_back(30);
         assert_eq!(list.remove(1), Ok(20));
         assert_eq!(list.get(1), Ok(30));
-        assert_eq!(list.remove(4), Err(LinkedListError::OutOfBounds));
+        assert_eq!(
+            list.remove(4),
+            Err(LinkedListError::OutOfBounds { index: 4, len: 2 })
+        );
         assert_eq!(list.remove(1), Ok(30));
         assert_eq!(list.remove(0), Ok(10));
         assert_eq!(list.remove(20), Err(LinkedListError::EmptyList));
@@ -479,6 +1148,10 @@ mod linked_list_tests {
         assert_eq!(list.get(3).unwrap(), 4);
         assert_eq!(list.get(4).unwrap(), 5);
         assert_eq!(list.get(5).unwrap(), 6);
+
+        list.append(LinkedList::new());
+        assert_eq!(list.length(), 6);
+        assert_eq!(list.back(), Ok(6));
     }
 
     #[test]
@@ -495,4 +1168,394 @@ mod linked_list_tests {
         assert_eq!(list.get(4).unwrap(), 5);
         assert_eq!(list.get(5).unwrap(), 6);
     }
+
+    #[test]
+    fn test_insert() {
+        let mut list = LinkedList::new();
+
+        assert_eq!(
+            list.insert(1, 10),
+            Err(LinkedListError::OutOfBounds { index: 1, len: 0 })
+        );
+        assert_eq!(list.insert(0, 20), Ok(()));
+        assert_eq!(list.insert(0, 10), Ok(()));
+        assert_eq!(list.insert(2, 40), Ok(()));
+        assert_eq!(list.insert(2, 30), Ok(()));
+        assert_eq!(
+            list.insert(5, 50),
+            Err(LinkedListError::OutOfBounds { index: 5, len: 4 })
+        );
+
+        assert_eq!(list.length(), 4);
+        assert_eq!(list.get(0), Ok(10));
+        assert_eq!(list.get(1), Ok(20));
+        assert_eq!(list.get(2), Ok(30));
+        assert_eq!(list.get(3), Ok(40));
+        assert_eq!(list.back(), Ok(40));
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list = LinkedList::from([1, 2, 3, 4, 5]);
+
+        assert!(list.split_off(6).is_err());
+
+        let mut other = list.split_off(3).unwrap();
+        assert_eq!(list.length(), 3);
+        assert_eq!(list.back(), Ok(3));
+        assert_eq!(other.length(), 2);
+        assert_eq!(other.front(), Ok(4));
+        assert_eq!(other.back(), Ok(5));
+
+        list.push_back(6);
+        assert_eq!(list.get(3), Ok(6));
+        assert_eq!(other.length(), 2);
+
+        let rest = other.split_off(0).unwrap();
+        assert!(other.is_empty());
+        assert_eq!(other.back(), Err(LinkedListError::EmptyList));
+        assert_eq!(rest.length(), 2);
+
+        let empty = list.split_off(4).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(list.length(), 4);
+    }
+
+    #[test]
+    fn test_splice() {
+        let mut list = LinkedList::from([1, 5]);
+
+        assert_eq!(
+            list.splice(3, LinkedList::from([9])),
+            Err(LinkedListError::OutOfBounds { index: 3, len: 2 })
+        );
+
+        list.splice(1, LinkedList::from([2, 3, 4])).unwrap();
+        list.splice(0, LinkedList::from([0])).unwrap();
+        list.splice(6, LinkedList::from([6, 7])).unwrap();
+        list.splice(2, LinkedList::new()).unwrap();
+
+        assert_eq!(list.length(), 8);
+        for i in 0..8 {
+            assert_eq!(list.get(i), Ok(i as i32));
+        }
+        assert_eq!(list.back(), Ok(7));
+
+        let mut empty = LinkedList::new();
+        empty.splice(0, LinkedList::from([1, 2])).unwrap();
+        assert_eq!(empty.length(), 2);
+        assert_eq!(empty.front(), Ok(1));
+        assert_eq!(empty.back(), Ok(2));
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut list = LinkedList::from([5, 3, 9, 1, 7, 2, 8, 2, 0]);
+        list.sort();
+
+        let expected = [0, 1, 2, 2, 3, 5, 7, 8, 9];
+        assert_eq!(list.length(), expected.len());
+        for (i, value) in expected.into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+        assert_eq!(list.back(), Ok(9));
+
+        list.push_back(10);
+        assert_eq!(list.get(9), Ok(10));
+
+        let mut list: LinkedList<i32> = LinkedList::new();
+        list.sort();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_sort_by() {
+        let mut list = LinkedList::from([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')]);
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let expected = [(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')];
+        for (i, value) in expected.into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+
+        list.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        assert_eq!(list.front(), Ok((0, 'e')));
+        assert_eq!(list.back(), Ok((2, 'a')));
+    }
+
+    #[test]
+    fn test_sort_by_key() {
+        let mut list = LinkedList::from([-4, 3, -1, 2, 0]);
+        list.sort_by_key(|value: &i32| value.abs());
+
+        let expected = [0, -1, 2, 3, -4];
+        for (i, value) in expected.into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let mut list = LinkedList::from([1, 4, 6, 9]);
+        list.merge_sorted(LinkedList::from([0, 2, 4, 10, 11]));
+
+        let expected = [0, 1, 2, 4, 4, 6, 9, 10, 11];
+        assert_eq!(list.length(), expected.len());
+        for (i, value) in expected.into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+        assert_eq!(list.back(), Ok(11));
+
+        let mut empty = LinkedList::new();
+        empty.merge_sorted(LinkedList::from([1, 2]));
+        assert_eq!(empty.length(), 2);
+        assert_eq!(empty.back(), Ok(2));
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut list = LinkedList::from([1, 2, 3, 4]);
+        list.reverse();
+
+        for (i, value) in [4, 3, 2, 1].into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+        assert_eq!(list.front(), Ok(4));
+        assert_eq!(list.back(), Ok(1));
+
+        list.push_back(0);
+        assert_eq!(list.get(4), Ok(0));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list = LinkedList::from([1, 2, 3, 4, 5, 6]);
+        list.retain(|value| value % 2 == 0);
+
+        assert_eq!(list.length(), 3);
+        assert_eq!(list.get(0), Ok(2));
+        assert_eq!(list.get(1), Ok(4));
+        assert_eq!(list.get(2), Ok(6));
+        assert_eq!(list.back(), Ok(6));
+
+        list.retain(|value| *value < 6);
+        assert_eq!(list.back(), Ok(4));
+
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.back(), Err(LinkedListError::EmptyList));
+    }
+
+    #[test]
+    fn test_retain_mut() {
+        let mut list = LinkedList::from([1, 2, 3, 4]);
+        list.retain_mut(|value| {
+            *value *= 10;
+            *value != 20
+        });
+
+        assert_eq!(list.length(), 3);
+        assert_eq!(list.get(0), Ok(10));
+        assert_eq!(list.get(1), Ok(30));
+        assert_eq!(list.get(2), Ok(40));
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut list = LinkedList::from([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        list.dedup();
+
+        assert_eq!(list.length(), 5);
+        for (i, value) in [1, 2, 3, 1, 4].into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+        assert_eq!(list.back(), Ok(4));
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        let mut list = LinkedList::from([10, 11, 20, 21, 22, 30]);
+        list.dedup_by_key(|value| *value / 10);
+
+        assert_eq!(list.length(), 3);
+        assert_eq!(list.get(0), Ok(10));
+        assert_eq!(list.get(1), Ok(20));
+        assert_eq!(list.get(2), Ok(30));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut list = LinkedList::from([1, 2, 3, 4, 5]);
+
+        list.rotate_left(2);
+        for (i, value) in [3, 4, 5, 1, 2].into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+        assert_eq!(list.back(), Ok(2));
+
+        list.rotate_right(2);
+        for (i, value) in [1, 2, 3, 4, 5].into_iter().enumerate() {
+            assert_eq!(list.get(i), Ok(value));
+        }
+        assert_eq!(list.back(), Ok(5));
+
+        list.rotate_right(6);
+        assert_eq!(list.front(), Ok(5));
+        assert_eq!(list.back(), Ok(4));
+
+        list.rotate_left(5);
+        assert_eq!(list.front(), Ok(5));
+    }
+
+    #[test]
+    fn test_extract_if() {
+        let mut list = LinkedList::from([1, 2, 3, 4, 5, 6]);
+
+        let mut evens = list.extract_if(|value| *value % 2 == 0);
+        assert_eq!(evens.next(), Some(2));
+        assert_eq!(evens.next(), Some(4));
+        assert_eq!(evens.next(), Some(6));
+        assert_eq!(evens.next(), None);
+
+        assert_eq!(list.length(), 3);
+        assert_eq!(list.get(0), Ok(1));
+        assert_eq!(list.get(1), Ok(3));
+        assert_eq!(list.get(2), Ok(5));
+        assert_eq!(list.back(), Ok(5));
+
+        let extracted = list.extract_if(|value| *value < 4).count();
+        assert_eq!(extracted, 2);
+        assert_eq!(list.length(), 1);
+        assert_eq!(list.front(), Ok(5));
+        assert_eq!(list.back(), Ok(5));
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let mut list = LinkedList::new();
+        for i in 0..10_000_000u64 {
+            list.push_back(i);
+        }
+
+        assert_eq!(list.length(), 10_000_000);
+        drop(list);
+    }
+
+    #[test]
+    fn test_iter() {
+        let list = LinkedList::from([1, 2, 3]);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
+
+        let mut sum = 0;
+        for value in &list {
+            sum += value;
+        }
+        assert_eq!(sum, 6);
+
+        let values: Vec<i32> = list.into_iter().collect();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_debug() {
+        let list = LinkedList::from([1, 2, 3]);
+        assert_eq!(format!("{list:?}"), "[1, 2, 3]");
+
+        let list: LinkedList<i32> = LinkedList::default();
+        assert_eq!(format!("{list:?}"), "[]");
+    }
+
+    #[test]
+    fn test_clone() {
+        let list = LinkedList::from([1, 2, 3]);
+        let mut other = list.clone();
+
+        other.push_back(4);
+        other.retain_mut(|value| {
+            *value *= 2;
+            true
+        });
+
+        assert_eq!(list, LinkedList::from([1, 2, 3]));
+        assert_eq!(other, LinkedList::from([2, 4, 6, 8]));
+    }
+
+    #[test]
+    fn test_eq_and_ord() {
+        let a = LinkedList::from([1, 2, 3]);
+        let b = LinkedList::from([1, 2, 4]);
+        let c = LinkedList::from([1, 2]);
+
+        assert_eq!(a, LinkedList::from([1, 2, 3]));
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+        assert!(a < b);
+        assert!(c < a);
+        assert_eq!(a.cmp(&a.clone()), std::cmp::Ordering::Equal);
+
+        let x = LinkedList::from([1.0, f64::NAN]);
+        assert_eq!(x.partial_cmp(&x), None);
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash_of(list: &LinkedList<i32>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            list.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let list = LinkedList::from([1, 2, 3]);
+        assert_eq!(hash_of(&list), hash_of(&list.clone()));
+        assert_ne!(hash_of(&list), hash_of(&LinkedList::from([3, 2, 1])));
+        assert_ne!(hash_of(&LinkedList::new()), hash_of(&LinkedList::from([0])));
+    }
+
+    #[test]
+    fn test_extend_and_collect() {
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        list.extend(vec![4, 5]);
+        list.extend(&[6, 7]);
+
+        assert_eq!(list.length(), 7);
+        assert_eq!(list.back(), Ok(7));
+        assert_eq!(list, LinkedList::from([1, 2, 3, 4, 5, 6, 7]));
+    }
+
+    #[test]
+    fn test_vec_conversion() {
+        let list = LinkedList::from(vec![1, 2, 3]);
+        assert_eq!(list.length(), 3);
+        assert_eq!(list.back(), Ok(3));
+
+        let vec: Vec<i32> = list.into();
+        assert_eq!(vec, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_error_display() {
+        let list = LinkedList::from([1, 2]);
+
+        let err = list.get(5).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "index 5 is out of bounds for a list of length 2"
+        );
+        assert_eq!(LinkedListError::EmptyList.to_string(), "the list is empty");
+        assert_eq!(
+            LinkedListError::Full { capacity: 4 }.to_string(),
+            "the list is full at its capacity of 4"
+        );
+
+        let err: Box<dyn std::error::Error> = Box::new(err);
+        assert!(err.source().is_none());
+    }
 }
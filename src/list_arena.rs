@@ -0,0 +1,603 @@
+use std::{fmt, marker::PhantomData};
+
+// Marks the end of a list, and of the free list.
+const NIL: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArenaError {
+    // The handle was never issued here, or its list was removed.
+    InvalidList,
+    // The node was removed, or its slot was reused or compacted away.
+    InvalidNode,
+    EmptyList,
+    // Every `u32` index is in use.
+    Full,
+}
+
+impl fmt::Display for ArenaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArenaError::InvalidList => write!(f, "the list handle is no longer valid"),
+            ArenaError::InvalidNode => write!(f, "the node handle is no longer valid"),
+            ArenaError::EmptyList => write!(f, "the list is empty"),
+            ArenaError::Full => write!(f, "the arena has run out of node indices"),
+        }
+    }
+}
+
+impl std::error::Error for ArenaError {}
+
+// Handles carry the generation of their slot, so a handle to a removed node
+// or list is rejected instead of silently reaching whatever reused the slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListId {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: u32,
+    generation: u32,
+}
+
+// A vacant slot has no value and uses `next` to chain the free list.
+struct Slot<T> {
+    value: Option<T>,
+    generation: u32,
+    list: u32,
+    prev: u32,
+    next: u32,
+}
+
+struct Header {
+    head: u32,
+    tail: u32,
+    len: usize,
+    generation: u32,
+    live: bool,
+}
+
+// Any number of doubly linked lists sharing one pool of nodes. Links are
+// `u32` indices into a single `Vec`, freed slots are reused before the pool
+// grows, and moving a node to another list only rewrites links.
+pub struct ListArena<T> {
+    slots: Vec<Slot<T>>,
+    free: u32,
+    lists: Vec<Header>,
+    free_lists: Vec<u32>,
+    len: usize,
+    // Above every generation any handle has seen, so compaction can hand out
+    // ids that cannot match an old one.
+    max_generation: u32,
+}
+
+impl<T> ListArena<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(nodes: usize) -> Self {
+        ListArena {
+            slots: Vec::with_capacity(nodes),
+            free: NIL,
+            lists: Vec::new(),
+            free_lists: Vec::new(),
+            len: 0,
+            max_generation: 0,
+        }
+    }
+
+    // Live nodes across every list.
+    pub fn node_count(&self) -> usize {
+        self.len
+    }
+
+    // Slots held by the pool, live or free.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn new_list(&mut self) -> ListId {
+        if let Some(index) = self.free_lists.pop() {
+            let header = &mut self.lists[index as usize];
+            header.live = true;
+            return ListId {
+                index,
+                generation: header.generation,
+            };
+        }
+
+        self.lists.push(Header {
+            head: NIL,
+            tail: NIL,
+            len: 0,
+            generation: 0,
+            live: true,
+        });
+        ListId {
+            index: (self.lists.len() - 1) as u32,
+            generation: 0,
+        }
+    }
+
+    // Frees the list's nodes and invalidates the handle.
+    pub fn remove_list(&mut self, list: ListId) -> Result<(), ArenaError> {
+        self.clear(list)?;
+
+        let header = &mut self.lists[list.index as usize];
+        header.live = false;
+        header.generation = header.generation.wrapping_add(1);
+        self.free_lists.push(list.index);
+        Ok(())
+    }
+
+    pub fn clear(&mut self, list: ListId) -> Result<(), ArenaError> {
+        let mut index = self.header(list)?.head;
+        while index != NIL {
+            let next = self.slots[index as usize].next;
+            self.release(index);
+            index = next;
+        }
+
+        let header = &mut self.lists[list.index as usize];
+        self.len -= header.len;
+        header.head = NIL;
+        header.tail = NIL;
+        header.len = 0;
+        Ok(())
+    }
+
+    pub fn len(&self, list: ListId) -> Result<usize, ArenaError> {
+        Ok(self.header(list)?.len)
+    }
+
+    pub fn is_empty(&self, list: ListId) -> Result<bool, ArenaError> {
+        Ok(self.header(list)?.len == 0)
+    }
+
+    pub fn push_front(&mut self, list: ListId, value: T) -> Result<NodeId, ArenaError> {
+        let head = self.header(list)?.head;
+        let index = self.alloc(value)?;
+        self.link(index, list.index, NIL, head);
+        Ok(self.id(index))
+    }
+
+    pub fn push_back(&mut self, list: ListId, value: T) -> Result<NodeId, ArenaError> {
+        let tail = self.header(list)?.tail;
+        let index = self.alloc(value)?;
+        self.link(index, list.index, tail, NIL);
+        Ok(self.id(index))
+    }
+
+    pub fn insert_after(&mut self, node: NodeId, value: T) -> Result<NodeId, ArenaError> {
+        let (list, next) = {
+            let slot = self.slot(node)?;
+            (slot.list, slot.next)
+        };
+        let index = self.alloc(value)?;
+        self.link(index, list, node.index, next);
+        Ok(self.id(index))
+    }
+
+    pub fn insert_before(&mut self, node: NodeId, value: T) -> Result<NodeId, ArenaError> {
+        let (list, prev) = {
+            let slot = self.slot(node)?;
+            (slot.list, slot.prev)
+        };
+        let index = self.alloc(value)?;
+        self.link(index, list, prev, node.index);
+        Ok(self.id(index))
+    }
+
+    pub fn pop_front(&mut self, list: ListId) -> Result<T, ArenaError> {
+        let head = self.header(list)?.head;
+        if head == NIL {
+            return Err(ArenaError::EmptyList);
+        }
+        self.unlink(head);
+        Ok(self.release(head))
+    }
+
+    pub fn pop_back(&mut self, list: ListId) -> Result<T, ArenaError> {
+        let tail = self.header(list)?.tail;
+        if tail == NIL {
+            return Err(ArenaError::EmptyList);
+        }
+        self.unlink(tail);
+        Ok(self.release(tail))
+    }
+
+    // O(1) from the handle alone, wherever the node sits.
+    pub fn remove(&mut self, node: NodeId) -> Result<T, ArenaError> {
+        self.slot(node)?;
+        self.unlink(node.index);
+        Ok(self.release(node.index))
+    }
+
+    // Relinks the node at the front of `list`, which may be the list it is
+    // already in. The node keeps its id and its slot.
+    pub fn move_to_front(&mut self, node: NodeId, list: ListId) -> Result<(), ArenaError> {
+        self.slot(node)?;
+        self.header(list)?;
+
+        self.unlink(node.index);
+        let head = self.lists[list.index as usize].head;
+        self.link(node.index, list.index, NIL, head);
+        Ok(())
+    }
+
+    pub fn move_to_back(&mut self, node: NodeId, list: ListId) -> Result<(), ArenaError> {
+        self.slot(node)?;
+        self.header(list)?;
+
+        self.unlink(node.index);
+        let tail = self.lists[list.index as usize].tail;
+        self.link(node.index, list.index, tail, NIL);
+        Ok(())
+    }
+
+    pub fn get(&self, node: NodeId) -> Result<&T, ArenaError> {
+        match &self.slot(node)?.value {
+            Some(value) => Ok(value),
+            None => Err(ArenaError::InvalidNode),
+        }
+    }
+
+    pub fn get_mut(&mut self, node: NodeId) -> Result<&mut T, ArenaError> {
+        self.slot(node)?;
+        match &mut self.slots[node.index as usize].value {
+            Some(value) => Ok(value),
+            None => Err(ArenaError::InvalidNode),
+        }
+    }
+
+    pub fn list_of(&self, node: NodeId) -> Result<ListId, ArenaError> {
+        let list = self.slot(node)?.list;
+        Ok(ListId {
+            index: list,
+            generation: self.lists[list as usize].generation,
+        })
+    }
+
+    pub fn front(&self, list: ListId) -> Result<Option<NodeId>, ArenaError> {
+        let head = self.header(list)?.head;
+        Ok(self.maybe_id(head))
+    }
+
+    pub fn back(&self, list: ListId) -> Result<Option<NodeId>, ArenaError> {
+        let tail = self.header(list)?.tail;
+        Ok(self.maybe_id(tail))
+    }
+
+    pub fn next(&self, node: NodeId) -> Result<Option<NodeId>, ArenaError> {
+        let next = self.slot(node)?.next;
+        Ok(self.maybe_id(next))
+    }
+
+    pub fn prev(&self, node: NodeId) -> Result<Option<NodeId>, ArenaError> {
+        let prev = self.slot(node)?.prev;
+        Ok(self.maybe_id(prev))
+    }
+
+    pub fn iter(&self, list: ListId) -> Result<Iter<'_, T>, ArenaError> {
+        let header = self.header(list)?;
+        Ok(Iter {
+            arena: self,
+            index: header.head,
+            remaining: header.len,
+            marker: PhantomData,
+        })
+    }
+
+    // Drops the free slots and lays every list out contiguously in list
+    // order. Node ids change, so `relocated` is called with the old and new
+    // id of every live node; old ids are rejected afterwards. List ids stay
+    // valid.
+    pub fn compact<F>(&mut self, mut relocated: F)
+    where
+        F: FnMut(NodeId, NodeId),
+    {
+        let generation = self.max_generation.wrapping_add(1);
+        let mut old = std::mem::replace(&mut self.slots, Vec::with_capacity(self.len));
+
+        for (list, header) in self.lists.iter_mut().enumerate() {
+            if !header.live || header.head == NIL {
+                continue;
+            }
+
+            let mut index = header.head;
+            let mut prev = NIL;
+            while index != NIL {
+                let slot = &mut old[index as usize];
+                let new = self.slots.len() as u32;
+                relocated(
+                    NodeId {
+                        index,
+                        generation: slot.generation,
+                    },
+                    NodeId {
+                        index: new,
+                        generation,
+                    },
+                );
+
+                self.slots.push(Slot {
+                    value: slot.value.take(),
+                    generation,
+                    list: list as u32,
+                    prev,
+                    next: NIL,
+                });
+                if prev == NIL {
+                    header.head = new;
+                } else {
+                    self.slots[prev as usize].next = new;
+                }
+
+                prev = new;
+                index = slot.next;
+            }
+            header.tail = prev;
+        }
+
+        self.free = NIL;
+        self.max_generation = generation;
+    }
+
+    fn header(&self, list: ListId) -> Result<&Header, ArenaError> {
+        match self.lists.get(list.index as usize) {
+            Some(header) if header.live && header.generation == list.generation => Ok(header),
+            _ => Err(ArenaError::InvalidList),
+        }
+    }
+
+    fn slot(&self, node: NodeId) -> Result<&Slot<T>, ArenaError> {
+        match self.slots.get(node.index as usize) {
+            Some(slot) if slot.value.is_some() && slot.generation == node.generation => Ok(slot),
+            _ => Err(ArenaError::InvalidNode),
+        }
+    }
+
+    fn id(&self, index: u32) -> NodeId {
+        NodeId {
+            index,
+            generation: self.slots[index as usize].generation,
+        }
+    }
+
+    fn maybe_id(&self, index: u32) -> Option<NodeId> {
+        (index != NIL).then(|| self.id(index))
+    }
+
+    // Takes a slot off the free list, or grows the pool. The slot is not
+    // linked into any list yet.
+    fn alloc(&mut self, value: T) -> Result<u32, ArenaError> {
+        if self.free != NIL {
+            let index = self.free;
+            let slot = &mut self.slots[index as usize];
+            self.free = slot.next;
+            slot.value = Some(value);
+            return Ok(index);
+        }
+
+        if self.slots.len() >= NIL as usize {
+            return Err(ArenaError::Full);
+        }
+        self.slots.push(Slot {
+            value: Some(value),
+            generation: self.max_generation,
+            list: NIL,
+            prev: NIL,
+            next: NIL,
+        });
+        Ok((self.slots.len() - 1) as u32)
+    }
+
+    // Empties a slot that is already unlinked and puts it on the free list.
+    fn release(&mut self, index: u32) -> T {
+        let slot = &mut self.slots[index as usize];
+        slot.generation = slot.generation.wrapping_add(1);
+        self.max_generation = self.max_generation.max(slot.generation);
+        slot.list = NIL;
+        slot.prev = NIL;
+        slot.next = self.free;
+        self.free = index;
+
+        match slot.value.take() {
+            Some(value) => value,
+            None => unreachable!("only live nodes are released"),
+        }
+    }
+
+    fn link(&mut self, index: u32, list: u32, prev: u32, next: u32) {
+        {
+            let slot = &mut self.slots[index as usize];
+            slot.list = list;
+            slot.prev = prev;
+            slot.next = next;
+        }
+
+        let header = &mut self.lists[list as usize];
+        match prev {
+            NIL => header.head = index,
+            prev => self.slots[prev as usize].next = index,
+        }
+        match next {
+            NIL => header.tail = index,
+            next => self.slots[next as usize].prev = index,
+        }
+        header.len += 1;
+        self.len += 1;
+    }
+
+    fn unlink(&mut self, index: u32) {
+        let (list, prev, next) = {
+            let slot = &self.slots[index as usize];
+            (slot.list, slot.prev, slot.next)
+        };
+
+        let header = &mut self.lists[list as usize];
+        match prev {
+            NIL => header.head = next,
+            prev => self.slots[prev as usize].next = next,
+        }
+        match next {
+            NIL => header.tail = prev,
+            next => self.slots[next as usize].prev = prev,
+        }
+        header.len -= 1;
+        self.len -= 1;
+    }
+}
+
+impl<T> Default for ListArena<T> {
+    fn default() -> Self {
+        ListArena::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    arena: &'a ListArena<T>,
+    index: u32,
+    remaining: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (NodeId, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == NIL {
+            return None;
+        }
+
+        let slot = &self.arena.slots[self.index as usize];
+        let id = NodeId {
+            index: self.index,
+            generation: slot.generation,
+        };
+        self.index = slot.next;
+        self.remaining -= 1;
+        slot.value.as_ref().map(|value| (id, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod list_arena_tests {
+    use std::collections::HashMap;
+
+    use super::{ArenaError, ListArena};
+
+    fn values<T: Copy>(arena: &ListArena<T>, list: super::ListId) -> Vec<T> {
+        arena.iter(list).unwrap().map(|(_, value)| *value).collect()
+    }
+
+    #[test]
+    fn test_lists_share_one_pool() {
+        let mut arena = ListArena::new();
+        let left = arena.new_list();
+        let right = arena.new_list();
+
+        let a = arena.push_back(left, 'a').unwrap();
+        let c = arena.push_back(left, 'c').unwrap();
+        arena.insert_after(a, 'b').unwrap();
+        arena.push_front(right, 'y').unwrap();
+        let z = arena
+            .insert_before(arena.front(right).unwrap().unwrap(), 'x')
+            .unwrap();
+        arena.push_back(right, 'z').unwrap();
+        assert_eq!(values(&arena, left), ['a', 'b', 'c']);
+        assert_eq!(values(&arena, right), ['x', 'y', 'z']);
+        assert_eq!(arena.node_count(), 6);
+
+        // Moving relinks the node in place; the id keeps working.
+        arena.move_to_back(a, right).unwrap();
+        arena.move_to_front(c, left).unwrap();
+        assert_eq!(values(&arena, left), ['c', 'b']);
+        assert_eq!(values(&arena, right), ['x', 'y', 'z', 'a']);
+        assert_eq!(arena.list_of(a), Ok(right));
+        assert_eq!(
+            arena.prev(a).unwrap().map(|id| arena.get(id).copied()),
+            Some(Ok('z'))
+        );
+        assert_eq!(arena.capacity(), 6);
+
+        assert_eq!(arena.remove(z), Ok('x'));
+        assert_eq!(arena.pop_back(right), Ok('a'));
+        assert_eq!(arena.pop_front(left), Ok('c'));
+        *arena.get_mut(arena.back(right).unwrap().unwrap()).unwrap() = 'Z';
+        assert_eq!(values(&arena, right), ['y', 'Z']);
+        assert_eq!(arena.len(left), Ok(1));
+    }
+
+    #[test]
+    fn test_stale_handles_and_reuse() {
+        let mut arena = ListArena::new();
+        let list = arena.new_list();
+        let first = arena.push_back(list, 1).unwrap();
+        arena.push_back(list, 2).unwrap();
+
+        assert_eq!(arena.remove(first), Ok(1));
+        assert_eq!(arena.get(first), Err(ArenaError::InvalidNode));
+        assert_eq!(arena.remove(first), Err(ArenaError::InvalidNode));
+
+        // The freed slot is reused, but the old id does not reach it.
+        let third = arena.push_back(list, 3).unwrap();
+        assert_eq!(arena.capacity(), 2);
+        assert_ne!(third, first);
+        assert_eq!(arena.get(first), Err(ArenaError::InvalidNode));
+
+        arena.remove_list(list).unwrap();
+        assert_eq!(arena.node_count(), 0);
+        assert_eq!(arena.push_back(list, 4), Err(ArenaError::InvalidList));
+        assert_eq!(arena.get(third), Err(ArenaError::InvalidNode));
+
+        let reused = arena.new_list();
+        assert_ne!(reused, list);
+        assert_eq!(arena.pop_front(reused), Err(ArenaError::EmptyList));
+        assert_eq!(arena.is_empty(reused), Ok(true));
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut arena = ListArena::new();
+        let odd = arena.new_list();
+        let even = arena.new_list();
+        let mut ids = Vec::new();
+        for value in 0..20 {
+            let list = if value % 2 == 0 { even } else { odd };
+            ids.push(arena.push_back(list, value).unwrap());
+        }
+        for &id in ids.iter().step_by(3) {
+            arena.remove(id).unwrap();
+        }
+        assert_eq!(arena.capacity(), 20);
+
+        let mut moved = HashMap::new();
+        arena.compact(|old, new| {
+            moved.insert(old, new);
+        });
+
+        assert_eq!(arena.capacity(), arena.node_count());
+        assert_eq!(moved.len(), 13);
+        assert_eq!(values(&arena, odd), [1, 5, 7, 11, 13, 17, 19]);
+        assert_eq!(values(&arena, even), [2, 4, 8, 10, 14, 16]);
+        for (old, new) in moved {
+            assert_eq!(arena.get(old), Err(ArenaError::InvalidNode));
+            assert!(arena.get(new).is_ok());
+        }
+
+        arena.push_front(odd, -1).unwrap();
+        assert_eq!(
+            arena.front(odd).map(|id| arena.get(id.unwrap()).copied()),
+            Ok(Ok(-1))
+        );
+    }
+}
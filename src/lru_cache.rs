@@ -0,0 +1,310 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use crate::avl_tree::AVLTree;
+
+type NodeRef<K, V> = Rc<RefCell<Node<K, V>>>;
+type Link<K, V> = Option<NodeRef<K, V>>;
+
+// The recency list runs from the most recently used entry at `head` to the
+// least recently used at `tail`. `prev` is weak so the list holds no cycles;
+// the index and the `next` chain own the nodes.
+//
+// This is its own list rather than `linked_list::LinkedList`. That list is
+// singly linked and hands out values rather than nodes, so promoting or
+// evicting an entry would mean walking to its predecessor, and it only holds
+// `T: Copy + PartialEq`, which cached values need not be.
+struct Node<K, V> {
+    key: K,
+    value: V,
+    prev: Option<Weak<RefCell<Node<K, V>>>>,
+    next: Link<K, V>,
+}
+
+pub struct LruCache<K, V>
+where
+    K: Ord + Clone,
+{
+    index: AVLTree<K, NodeRef<K, V>>,
+    head: Link<K, V>,
+    tail: Link<K, V>,
+    capacity: usize,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Ord + Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            index: AVLTree::new(),
+            head: None,
+            tail: None,
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    // Called with every entry pushed out by `put` or `resize`, but not with
+    // entries taken out by `pop_lru`, `remove` or `clear`.
+    pub fn on_evict<F>(&mut self, callback: F)
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    // Marks the entry as the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let node = self.index.get(key.clone()).ok()?;
+        self.detach(&node);
+        self.attach_front(node.clone());
+
+        let value = node.borrow().value.clone();
+        Some(value)
+    }
+
+    // Like `get`, but leaves the recency order alone.
+    pub fn peek(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let node = self.index.get(key.clone()).ok()?;
+        let value = node.borrow().value.clone();
+        Some(value)
+    }
+
+    // Returns the old value when the key was already cached. A new key at
+    // capacity evicts the least recently used entry first.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Ok(node) = self.index.get(key.clone()) {
+            self.detach(&node);
+            self.attach_front(node.clone());
+            return Some(std::mem::replace(&mut node.borrow_mut().value, value));
+        }
+
+        if self.capacity == 0 {
+            self.evict(key, value);
+            return None;
+        }
+
+        while self.len() >= self.capacity {
+            let Some((key, value)) = self.pop_lru() else {
+                break;
+            };
+            self.evict(key, value);
+        }
+
+        let node = Rc::new(RefCell::new(Node {
+            key: key.clone(),
+            value,
+            prev: None,
+            next: None,
+        }));
+        self.attach_front(node.clone());
+        let _ = self.index.insert(key, node);
+
+        None
+    }
+
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let tail = self.tail.clone()?;
+        let key = tail.borrow().key.clone();
+        Some(self.unlink(&key, tail))
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let node = self.index.get(key.clone()).ok()?;
+        Some(self.unlink(key, node).1)
+    }
+
+    // Shrinking below the current length evicts from the least recently used
+    // end.
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.len() > capacity {
+            let Some((key, value)) = self.pop_lru() else {
+                break;
+            };
+            self.evict(key, value);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.tail = None;
+
+        let mut node = self.head.take();
+        while let Some(temp) = node {
+            node = temp.borrow_mut().next.take();
+        }
+    }
+
+    // Entries from the most to the least recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (K, V)> + '_
+    where
+        V: Clone,
+    {
+        let mut node = self.head.clone();
+        std::iter::from_fn(move || {
+            let current = node.take()?;
+            let current = current.borrow();
+            node = current.next.clone();
+            Some((current.key.clone(), current.value.clone()))
+        })
+    }
+
+    fn evict(&mut self, key: K, value: V) {
+        if let Some(callback) = self.on_evict.as_mut() {
+            callback(key, value);
+        }
+    }
+
+    fn unlink(&mut self, key: &K, node: NodeRef<K, V>) -> (K, V) {
+        self.detach(&node);
+        let _ = self.index.remove(key.clone());
+
+        let Ok(node) = Rc::try_unwrap(node) else {
+            unreachable!("an unlinked node is only owned by the caller");
+        };
+        let node = node.into_inner();
+        (node.key, node.value)
+    }
+
+    fn detach(&mut self, node: &NodeRef<K, V>) {
+        let (prev, next) = {
+            let mut node = node.borrow_mut();
+            let prev = node.prev.take().and_then(|prev| prev.upgrade());
+            (prev, node.next.take())
+        };
+
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev,
+        }
+    }
+
+    fn attach_front(&mut self, node: NodeRef<K, V>) {
+        match self.head.take() {
+            Some(head) => {
+                head.borrow_mut().prev = Some(Rc::downgrade(&node));
+                node.borrow_mut().next = Some(head);
+            }
+            None => self.tail = Some(node.clone()),
+        }
+        self.head = Some(node);
+    }
+}
+
+impl<K, V> Drop for LruCache<K, V>
+where
+    K: Ord + Clone,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod lru_cache_tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::LruCache;
+
+    #[test]
+    fn test_put_and_get() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(cache.put(1, 'a'), None);
+        assert_eq!(cache.put(2, 'b'), None);
+        assert_eq!(cache.get(&1), Some('a'));
+
+        // 2 is now the least recently used, so it goes first.
+        assert_eq!(cache.put(3, 'c'), None);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.iter().collect::<Vec<_>>(), [(3, 'c'), (1, 'a')]);
+
+        assert_eq!(cache.put(1, 'A'), Some('a'));
+        assert_eq!(cache.put(4, 'd'), None);
+        assert_eq!(cache.iter().collect::<Vec<_>>(), [(4, 'd'), (1, 'A')]);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_peek_pop_and_remove() {
+        let mut cache = LruCache::new(3);
+        for key in 1..=3 {
+            cache.put(key, key * 10);
+        }
+
+        assert_eq!(cache.peek(&1), Some(10));
+        assert_eq!(cache.pop_lru(), Some((1, 10)));
+        assert_eq!(cache.remove(&3), Some(30));
+        assert_eq!(cache.remove(&3), None);
+        assert_eq!(cache.pop_lru(), Some((2, 20)));
+        assert_eq!(cache.pop_lru(), None);
+        assert!(cache.is_empty());
+
+        cache.put(5, 50);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(!cache.contains(&5));
+    }
+
+    #[test]
+    fn test_eviction_callback_and_resize() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let mut cache = LruCache::new(4);
+        let sink = evicted.clone();
+        cache.on_evict(move |key, value| sink.borrow_mut().push((key, value)));
+
+        for key in 0..6 {
+            cache.put(key, key);
+        }
+        assert_eq!(*evicted.borrow(), [(0, 0), (1, 1)]);
+
+        cache.get(&2);
+        cache.resize(2);
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(*evicted.borrow(), [(0, 0), (1, 1), (3, 3), (4, 4)]);
+        assert_eq!(cache.iter().collect::<Vec<_>>(), [(2, 2), (5, 5)]);
+
+        cache.resize(0);
+        cache.put(9, 9);
+        assert!(cache.is_empty());
+        assert_eq!(evicted.borrow().last(), Some(&(9, 9)));
+    }
+
+    #[test]
+    fn test_drop_large_cache() {
+        let mut cache = LruCache::new(200_000);
+        for key in 0..200_000 {
+            cache.put(key, key);
+        }
+    }
+}
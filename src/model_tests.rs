@@ -0,0 +1,530 @@
+// Differential tests: random operation sequences run on our containers and on
+// the std collection that models them, comparing every result and the full
+// contents after every step. A failing sequence is shrunk before it is
+// reported, so the panic message holds a short reproduction.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    avl_tree::{AVLTree, AVLTreeError},
+    linked_list::{LinkedList, LinkedListError},
+};
+
+const CASES: u64 = 1024;
+const MAX_OPS: usize = 64;
+// Small values and indices so duplicates, hits and out-of-bounds cases are
+// all common.
+const VALUES: u8 = 16;
+const INDICES: usize = 12;
+
+type Runner<Op> = fn(&[Op]) -> Result<(), String>;
+
+fn check<Op>(generate: fn(&mut StdRng) -> Op, run: Runner<Op>)
+where
+    Op: Clone + Debug,
+{
+    for seed in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let len = rng.gen_range(0..=MAX_OPS);
+        let ops: Vec<Op> = (0..len).map(|_| generate(&mut rng)).collect();
+
+        if failure(run, &ops).is_some() {
+            let minimal = shrink(ops, |ops| failure(run, ops).is_some());
+            let message = failure(run, &minimal).unwrap_or_default();
+            panic!(
+                "seed {seed}: {message}\nminimal sequence of {} ops: {minimal:#?}",
+                minimal.len()
+            );
+        }
+    }
+}
+
+// A panic inside the container counts as a failure too.
+fn failure<Op>(run: Runner<Op>, ops: &[Op]) -> Option<String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| run(ops))) {
+        Ok(Ok(())) => None,
+        Ok(Err(message)) => Some(message),
+        Err(payload) => Some(match payload.downcast_ref::<&str>() {
+            Some(message) => format!("panicked: {message}"),
+            None => match payload.downcast_ref::<String>() {
+                Some(message) => format!("panicked: {message}"),
+                None => String::from("panicked"),
+            },
+        }),
+    }
+}
+
+// Removes ever smaller chunks of the sequence for as long as it keeps failing,
+// ending with a sequence from which no single operation can be dropped.
+fn shrink<Op, F>(mut ops: Vec<Op>, fails: F) -> Vec<Op>
+where
+    Op: Clone,
+    F: Fn(&[Op]) -> bool,
+{
+    let mut chunk = ops.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed = false;
+
+        while start < ops.len() {
+            let mut candidate = ops.clone();
+            candidate.drain(start..(start + chunk).min(ops.len()));
+            if fails(&candidate) {
+                ops = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+
+        if !removed {
+            chunk /= 2;
+        }
+    }
+
+    ops
+}
+
+fn step_error<T: Debug>(
+    step: usize,
+    op: &impl Debug,
+    what: &str,
+    actual: T,
+    expected: T,
+) -> String {
+    format!("step {step}: {op:?}: {what} was {actual:?}, the model has {expected:?}")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ListOp {
+    PushFront(u8),
+    PushBack(u8),
+    PopFront,
+    PopBack,
+    Front,
+    Back,
+    Get(usize),
+    Insert(usize, u8),
+    Remove(usize),
+    Contains(u8),
+    SplitOff(usize),
+    Append(Vec<u8>),
+    Splice(usize, Vec<u8>),
+    Reverse,
+    Sort,
+    RotateLeft(usize),
+    RotateRight(usize),
+    RetainEven,
+    Dedup,
+    ExtractOdd,
+    Clear,
+}
+
+fn generate_list_op(rng: &mut StdRng) -> ListOp {
+    let value = rng.gen_range(0..VALUES);
+    let index = rng.gen_range(0..INDICES);
+    let values = |rng: &mut StdRng| {
+        let len = rng.gen_range(0..4);
+        (0..len).map(|_| rng.gen_range(0..VALUES)).collect()
+    };
+
+    match rng.gen_range(0..24) {
+        0..=2 => ListOp::PushFront(value),
+        3..=5 => ListOp::PushBack(value),
+        6 => ListOp::PopFront,
+        7 => ListOp::PopBack,
+        8 => ListOp::Front,
+        9 => ListOp::Back,
+        10 => ListOp::Get(index),
+        11 | 12 => ListOp::Insert(index, value),
+        13 => ListOp::Remove(index),
+        14 => ListOp::Contains(value),
+        15 => ListOp::SplitOff(index),
+        16 => ListOp::Append(values(rng)),
+        17 => ListOp::Splice(index, values(rng)),
+        18 => ListOp::Reverse,
+        19 => ListOp::Sort,
+        20 => ListOp::RotateLeft(index),
+        21 => ListOp::RotateRight(index),
+        22 => match rng.gen_range(0..3) {
+            0 => ListOp::RetainEven,
+            1 => ListOp::Dedup,
+            _ => ListOp::ExtractOdd,
+        },
+        _ => ListOp::Clear,
+    }
+}
+
+// The model's answer for `get` and `remove`, which report an empty list
+// before an out-of-bounds index.
+fn list_index_error(index: usize, len: usize) -> LinkedListError {
+    match len {
+        0 => LinkedListError::EmptyList,
+        len => LinkedListError::OutOfBounds { index, len },
+    }
+}
+
+fn list_bounds(index: usize, len: usize) -> Result<(), LinkedListError> {
+    match index <= len {
+        true => Ok(()),
+        false => Err(LinkedListError::OutOfBounds { index, len }),
+    }
+}
+
+// Every operation is reduced to the values it returned, so one comparison
+// covers them all.
+type ListResult = Result<Vec<u8>, LinkedListError>;
+
+fn apply_list(list: &mut LinkedList<u8>, op: &ListOp) -> ListResult {
+    let one = |value| vec![value];
+    match op {
+        ListOp::PushFront(value) => {
+            list.push_front(*value);
+            Ok(vec![])
+        }
+        ListOp::PushBack(value) => {
+            list.push_back(*value);
+            Ok(vec![])
+        }
+        ListOp::PopFront => list.pop_front().map(one),
+        ListOp::PopBack => list.pop_back().map(one),
+        ListOp::Front => list.front().map(one),
+        ListOp::Back => list.back().map(one),
+        ListOp::Get(index) => list.get(*index).map(one),
+        ListOp::Insert(index, value) => list.insert(*index, *value).map(|_| vec![]),
+        ListOp::Remove(index) => list.remove(*index).map(one),
+        ListOp::Contains(value) => Ok(vec![list.contains(*value) as u8]),
+        ListOp::SplitOff(index) => list.split_off(*index).map(Vec::from),
+        ListOp::Append(values) => {
+            list.append(LinkedList::from(values.clone()));
+            Ok(vec![])
+        }
+        ListOp::Splice(index, values) => list
+            .splice(*index, LinkedList::from(values.clone()))
+            .map(|_| vec![]),
+        ListOp::Reverse => {
+            list.reverse();
+            Ok(vec![])
+        }
+        ListOp::Sort => {
+            list.sort();
+            Ok(vec![])
+        }
+        ListOp::RotateLeft(k) => {
+            list.rotate_left(*k);
+            Ok(vec![])
+        }
+        ListOp::RotateRight(k) => {
+            list.rotate_right(*k);
+            Ok(vec![])
+        }
+        ListOp::RetainEven => {
+            list.retain(|value| value % 2 == 0);
+            Ok(vec![])
+        }
+        ListOp::Dedup => {
+            list.dedup();
+            Ok(vec![])
+        }
+        ListOp::ExtractOdd => Ok(list.extract_if(|value| *value % 2 == 1).collect()),
+        ListOp::Clear => {
+            list.clear();
+            Ok(vec![])
+        }
+    }
+}
+
+fn apply_list_model(model: &mut VecDeque<u8>, op: &ListOp) -> ListResult {
+    let len = model.len();
+    let empty = LinkedListError::EmptyList;
+    match op {
+        ListOp::PushFront(value) => {
+            model.push_front(*value);
+            Ok(vec![])
+        }
+        ListOp::PushBack(value) => {
+            model.push_back(*value);
+            Ok(vec![])
+        }
+        ListOp::PopFront => model.pop_front().map(|value| vec![value]).ok_or(empty),
+        ListOp::PopBack => model.pop_back().map(|value| vec![value]).ok_or(empty),
+        ListOp::Front => model.front().map(|value| vec![*value]).ok_or(empty),
+        ListOp::Back => model.back().map(|value| vec![*value]).ok_or(empty),
+        ListOp::Get(index) => model
+            .get(*index)
+            .map(|value| vec![*value])
+            .ok_or(list_index_error(*index, len)),
+        ListOp::Insert(index, value) => {
+            list_bounds(*index, len)?;
+            model.insert(*index, *value);
+            Ok(vec![])
+        }
+        ListOp::Remove(index) => model
+            .remove(*index)
+            .map(|value| vec![value])
+            .ok_or(list_index_error(*index, len)),
+        ListOp::Contains(value) => Ok(vec![model.contains(value) as u8]),
+        ListOp::SplitOff(index) => {
+            list_bounds(*index, len)?;
+            Ok(model.split_off(*index).into())
+        }
+        ListOp::Append(values) => {
+            model.extend(values);
+            Ok(vec![])
+        }
+        ListOp::Splice(index, values) => {
+            list_bounds(*index, len)?;
+            let tail = model.split_off(*index);
+            model.extend(values);
+            model.extend(tail);
+            Ok(vec![])
+        }
+        ListOp::Reverse => {
+            *model = model.iter().rev().copied().collect();
+            Ok(vec![])
+        }
+        ListOp::Sort => {
+            model.make_contiguous().sort();
+            Ok(vec![])
+        }
+        ListOp::RotateLeft(k) if len > 0 => {
+            model.rotate_left(k % len);
+            Ok(vec![])
+        }
+        ListOp::RotateRight(k) if len > 0 => {
+            model.rotate_right(k % len);
+            Ok(vec![])
+        }
+        ListOp::RotateLeft(_) | ListOp::RotateRight(_) => Ok(vec![]),
+        ListOp::RetainEven => {
+            model.retain(|value| value % 2 == 0);
+            Ok(vec![])
+        }
+        ListOp::Dedup => {
+            let mut values: Vec<u8> = model.drain(..).collect();
+            values.dedup();
+            model.extend(values);
+            Ok(vec![])
+        }
+        ListOp::ExtractOdd => {
+            let (odd, even) = model.iter().partition(|value| *value % 2 == 1);
+            *model = even;
+            Ok(odd.into())
+        }
+        ListOp::Clear => {
+            model.clear();
+            Ok(vec![])
+        }
+    }
+}
+
+fn run_list(ops: &[ListOp]) -> Result<(), String> {
+    let mut list = LinkedList::new();
+    let mut model = VecDeque::new();
+
+    for (step, op) in ops.iter().enumerate() {
+        let actual = apply_list(&mut list, op);
+        let expected = apply_list_model(&mut model, op);
+        if actual != expected {
+            return Err(step_error(step, op, "the result", actual, expected));
+        }
+
+        let contents: Vec<u8> = list.iter().collect();
+        let expected: Vec<u8> = model.iter().copied().collect();
+        if contents != expected {
+            return Err(step_error(step, op, "the list", contents, expected));
+        }
+        if list.length() != model.len() {
+            return Err(step_error(
+                step,
+                op,
+                "the length",
+                list.length(),
+                model.len(),
+            ));
+        }
+        if list.back().ok() != model.back().copied() {
+            return Err(step_error(
+                step,
+                op,
+                "the back",
+                list.back().ok(),
+                model.back().copied(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum MapOp {
+    Insert(u8, u8),
+    Replace(u8, u8),
+    Get(u8),
+    ContainsKey(u8),
+    Remove(u8),
+    First,
+    Last,
+    Range(u8, u8),
+    Clear,
+}
+
+fn generate_map_op(rng: &mut StdRng) -> MapOp {
+    let key = rng.gen_range(0..VALUES * 2);
+    let value = rng.gen();
+
+    match rng.gen_range(0..20) {
+        0..=6 => MapOp::Insert(key, value),
+        7 | 8 => MapOp::Replace(key, value),
+        9 | 10 => MapOp::Get(key),
+        11 => MapOp::ContainsKey(key),
+        12..=15 => MapOp::Remove(key),
+        16 => MapOp::First,
+        17 => MapOp::Last,
+        18 => MapOp::Range(key, rng.gen_range(0..VALUES * 2)),
+        _ => MapOp::Clear,
+    }
+}
+
+type MapResult = Result<Vec<(u8, u8)>, AVLTreeError<u8, u8>>;
+
+fn apply_map(tree: &mut AVLTree<u8, u8>, op: &MapOp) -> MapResult {
+    match op {
+        MapOp::Insert(key, value) => tree.insert(*key, *value).map(|_| vec![]),
+        MapOp::Replace(key, value) => tree.replace(*key, *value).map(|old| vec![(*key, old)]),
+        MapOp::Get(key) => tree.get(*key).map(|value| vec![(*key, value)]),
+        MapOp::ContainsKey(key) => Ok(vec![(*key, tree.contains_key(key) as u8)]),
+        MapOp::Remove(key) => tree.remove(*key).map(|value| vec![(*key, value)]),
+        MapOp::First => tree.first().map(|entry| vec![entry]),
+        MapOp::Last => tree.last().map(|entry| vec![entry]),
+        MapOp::Range(start, end) if start <= end => Ok(tree.range(*start..*end).collect()),
+        MapOp::Range(..) => Ok(vec![]),
+        MapOp::Clear => {
+            tree.clear();
+            Ok(vec![])
+        }
+    }
+}
+
+fn apply_map_model(model: &mut BTreeMap<u8, u8>, op: &MapOp) -> MapResult {
+    // Lookups on an empty tree report that before a missing key.
+    let missing = match model.is_empty() {
+        true => AVLTreeError::EmptyAVLTree,
+        false => AVLTreeError::NodeNotFound,
+    };
+
+    match op {
+        MapOp::Insert(key, value) => match model.contains_key(key) {
+            true => Err(AVLTreeError::AlreadyExists {
+                key: *key,
+                value: *value,
+            }),
+            false => {
+                model.insert(*key, *value);
+                Ok(vec![])
+            }
+        },
+        MapOp::Replace(key, value) => match model.get_mut(key) {
+            Some(old) => Ok(vec![(*key, std::mem::replace(old, *value))]),
+            None => Err(missing),
+        },
+        MapOp::Get(key) => model
+            .get(key)
+            .map(|value| vec![(*key, *value)])
+            .ok_or(missing),
+        MapOp::ContainsKey(key) => Ok(vec![(*key, model.contains_key(key) as u8)]),
+        MapOp::Remove(key) => model
+            .remove(key)
+            .map(|value| vec![(*key, value)])
+            .ok_or(missing),
+        MapOp::First => model
+            .first_key_value()
+            .map(|(key, value)| vec![(*key, *value)])
+            .ok_or(AVLTreeError::EmptyAVLTree),
+        MapOp::Last => model
+            .last_key_value()
+            .map(|(key, value)| vec![(*key, *value)])
+            .ok_or(AVLTreeError::EmptyAVLTree),
+        MapOp::Range(start, end) if start <= end => Ok(model
+            .range(*start..*end)
+            .map(|(key, value)| (*key, *value))
+            .collect()),
+        MapOp::Range(..) => Ok(vec![]),
+        MapOp::Clear => {
+            model.clear();
+            Ok(vec![])
+        }
+    }
+}
+
+// The tallest an AVL tree with `size` nodes can be.
+fn max_avl_height(size: usize) -> usize {
+    (1.4405 * ((size + 2) as f64).log2() - 0.3277).floor() as usize
+}
+
+fn run_map(ops: &[MapOp]) -> Result<(), String> {
+    let mut tree = AVLTree::new();
+    let mut model = BTreeMap::new();
+
+    for (step, op) in ops.iter().enumerate() {
+        let actual = apply_map(&mut tree, op);
+        let expected = apply_map_model(&mut model, op);
+        if actual != expected {
+            return Err(step_error(step, op, "the result", actual, expected));
+        }
+
+        let contents: Vec<(u8, u8)> = tree.iter().collect();
+        let expected: Vec<(u8, u8)> = model.iter().map(|(key, value)| (*key, *value)).collect();
+        if contents != expected {
+            return Err(step_error(step, op, "the tree", contents, expected));
+        }
+        if tree.size() != model.len() {
+            return Err(step_error(step, op, "the size", tree.size(), model.len()));
+        }
+        if tree.height() > max_avl_height(tree.size()) {
+            return Err(format!(
+                "step {step}: {op:?}: a tree of {} nodes is {} high",
+                tree.size(),
+                tree.height()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_linked_list_matches_vec_deque() {
+    check(generate_list_op, run_list);
+}
+
+#[test]
+fn test_avl_tree_matches_btree_map() {
+    check(generate_map_op, run_map);
+}
+
+#[test]
+fn test_shrink() {
+    // Stands in for a bug: popping the front after 3 was pushed.
+    let fails = |ops: &[ListOp]| {
+        let pushed = ops.iter().position(|op| *op == ListOp::PushBack(3));
+        let popped = ops.iter().rposition(|op| *op == ListOp::PopFront);
+        matches!((pushed, popped), (Some(pushed), Some(popped)) if pushed < popped)
+    };
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut ops: Vec<ListOp> = (0..40).map(|_| generate_list_op(&mut rng)).collect();
+    ops.insert(11, ListOp::PushBack(3));
+    ops.push(ListOp::PopFront);
+    assert!(fails(&ops));
+
+    assert_eq!(shrink(ops, fails), [ListOp::PushBack(3), ListOp::PopFront]);
+}
@@ -0,0 +1,260 @@
+use std::{
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::hazard::Domain;
+
+// The node `head` points at is a sentinel whose value has already been taken
+// (or never existed), so a node's value is only ever initialised while it
+// sits behind the sentinel.
+struct Node<T> {
+    value: MaybeUninit<T>,
+    next: AtomicPtr<Node<T>>,
+}
+
+impl<T> Node<T> {
+    fn new(value: MaybeUninit<T>) -> *mut Self {
+        Box::into_raw(Box::new(Node {
+            value,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+// The Michael-Scott lock-free FIFO. `tail` may lag one node behind the real
+// end; any thread that notices helps swing it forward before retrying.
+// Dequeued sentinels are reclaimed through hazard pointers.
+pub struct MsQueue<T> {
+    head: AtomicPtr<Node<T>>,
+    tail: AtomicPtr<Node<T>>,
+    domain: Domain,
+}
+
+unsafe impl<T: Send> Send for MsQueue<T> {}
+unsafe impl<T: Send> Sync for MsQueue<T> {}
+
+impl<T> MsQueue<T> {
+    pub fn new() -> Self {
+        let sentinel = Node::new(MaybeUninit::uninit());
+        MsQueue {
+            head: AtomicPtr::new(sentinel),
+            tail: AtomicPtr::new(sentinel),
+            domain: Domain::new(),
+        }
+    }
+
+    // Only a snapshot while other threads are enqueueing or dequeueing.
+    pub fn is_empty(&self) -> bool {
+        let guard = self.domain.guard();
+        let head = guard.protect(&self.head);
+        unsafe { (*head).next.load(Ordering::Acquire).is_null() }
+    }
+
+    pub fn enqueue(&self, value: T) {
+        let node = Node::new(MaybeUninit::new(value));
+        let guard = self.domain.guard();
+        loop {
+            let tail = guard.protect(&self.tail);
+            let next = unsafe { (*tail).next.load(Ordering::Acquire) };
+            if tail != self.tail.load(Ordering::Acquire) {
+                continue;
+            }
+
+            if !next.is_null() {
+                let _ =
+                    self.tail
+                        .compare_exchange(tail, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+
+            let linked = unsafe {
+                (*tail).next.compare_exchange(
+                    ptr::null_mut(),
+                    node,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                )
+            };
+            if linked.is_ok() {
+                let _ =
+                    self.tail
+                        .compare_exchange(tail, node, Ordering::Release, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    pub fn dequeue(&self) -> Option<T> {
+        let head_guard = self.domain.guard();
+        let next_guard = self.domain.guard();
+        loop {
+            let head = head_guard.protect(&self.head);
+            let tail = self.tail.load(Ordering::Acquire);
+            let next = unsafe { (*head).next.load(Ordering::Acquire) };
+            next_guard.set(next);
+
+            // While `head` is still the head, `next` is still linked behind it
+            // and so cannot have been retired before it was protected.
+            if head != self.head.load(Ordering::Acquire) {
+                continue;
+            }
+            if next.is_null() {
+                return None;
+            }
+            if head == tail {
+                let _ =
+                    self.tail
+                        .compare_exchange(tail, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                // `next` is the new sentinel; only this thread reads its value.
+                let value = unsafe { (*next).value.assume_init_read() };
+                drop(head_guard);
+                drop(next_guard);
+                unsafe { self.domain.retire(head) };
+                return Some(value);
+            }
+        }
+    }
+}
+
+impl<T> Default for MsQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for MsQueue<T> {
+    fn drop(&mut self) {
+        let sentinel = unsafe { Box::from_raw(*self.head.get_mut()) };
+        let mut node = sentinel.next.load(Ordering::Relaxed);
+        while !node.is_null() {
+            let mut current = unsafe { Box::from_raw(node) };
+            unsafe { current.value.assume_init_drop() };
+            node = current.next.load(Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod ms_queue_tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    use super::MsQueue;
+
+    #[test]
+    fn test_enqueue_and_dequeue() {
+        let queue = MsQueue::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+
+        for value in 0..5 {
+            queue.enqueue(value);
+        }
+        assert_eq!(queue.dequeue(), Some(0));
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(9);
+        assert_eq!(
+            std::iter::from_fn(|| queue.dequeue()).collect::<Vec<_>>(),
+            [2, 3, 4, 9]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_drops_every_value_once() {
+        struct Counted(Arc<AtomicUsize>);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let queue = MsQueue::new();
+        for _ in 0..1_000 {
+            queue.enqueue(Counted(dropped.clone()));
+        }
+        for _ in 0..600 {
+            drop(queue.dequeue());
+        }
+        assert_eq!(dropped.load(Ordering::Relaxed), 600);
+
+        drop(queue);
+        assert_eq!(dropped.load(Ordering::Relaxed), 1_000);
+    }
+
+    #[test]
+    fn test_many_producers_and_consumers() {
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const ITEMS: usize = 20_000;
+
+        let queue = Arc::new(MsQueue::new());
+        let remaining = Arc::new(AtomicUsize::new(PRODUCERS * ITEMS));
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    for item in 0..ITEMS {
+                        queue.enqueue((producer, item));
+                    }
+                })
+            })
+            .collect();
+
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let queue = queue.clone();
+                let remaining = remaining.clone();
+                thread::spawn(move || {
+                    let mut seen = vec![Vec::new(); PRODUCERS];
+                    while remaining.load(Ordering::Relaxed) > 0 {
+                        if let Some((producer, item)) = queue.dequeue() {
+                            remaining.fetch_sub(1, Ordering::Relaxed);
+                            seen[producer].push(item);
+                        }
+                    }
+                    seen
+                })
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let mut all = vec![Vec::new(); PRODUCERS];
+        for consumer in consumers {
+            for (producer, items) in consumer.join().unwrap().into_iter().enumerate() {
+                // A single consumer sees each producer's items in the order
+                // they were enqueued.
+                assert!(items.windows(2).all(|pair| pair[0] < pair[1]));
+                all[producer].extend(items);
+            }
+        }
+
+        // Nothing lost and nothing duplicated.
+        for mut items in all {
+            items.sort_unstable();
+            assert_eq!(items, (0..ITEMS).collect::<Vec<_>>());
+        }
+        assert!(queue.is_empty());
+    }
+}
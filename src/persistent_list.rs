@@ -0,0 +1,256 @@
+use std::{fmt, iter::FromIterator, rc::Rc};
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+// Nodes are immutable once built, so any number of lists can share a tail.
+// Each node remembers the length of the list it starts, which keeps `len` and
+// `tail` O(1).
+struct Node<T> {
+    value: T,
+    length: usize,
+    next: Link<T>,
+}
+
+// An immutable cons-list. Every "change" returns a new version and leaves the
+// old one usable, sharing whatever nodes the two have in common, so keeping a
+// history of versions costs one node per push.
+pub struct PersistentList<T> {
+    head: Link<T>,
+}
+
+impl<T> PersistentList<T> {
+    pub fn new() -> Self {
+        PersistentList { head: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.head.as_ref().map_or(0, |node| node.length)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    // O(1): the new list is one node in front of this one.
+    pub fn push_front(&self, value: T) -> Self {
+        PersistentList {
+            head: Some(Rc::new(Node {
+                value,
+                length: self.len() + 1,
+                next: self.head.clone(),
+            })),
+        }
+    }
+
+    // Everything after the head, shared with this list. `None` when empty.
+    pub fn tail(&self) -> Option<Self> {
+        let node = self.head.as_ref()?;
+        Some(PersistentList {
+            head: node.next.clone(),
+        })
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            node: self.head.as_deref(),
+        }
+    }
+
+    pub fn reverse(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.iter().fold(PersistentList::new(), |list, value| {
+            list.push_front(value.clone())
+        })
+    }
+
+    // Copies this list's nodes and shares all of `other`.
+    pub fn append(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        let values: Vec<&T> = self.iter().collect();
+        values
+            .into_iter()
+            .rev()
+            .fold(other.clone(), |list, value| list.push_front(value.clone()))
+    }
+
+    // Whether the two lists are the same version, not just equal.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(left), Some(right)) => Rc::ptr_eq(left, right),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+// Unlinks node by node and stops at the first node another list still holds,
+// so dropping a long list cannot overflow the stack.
+impl<T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        let mut node = self.head.take();
+        while let Some(current) = node {
+            match Rc::try_unwrap(current) {
+                Ok(mut current) => node = current.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    node: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.node?;
+        self.node = node.next.as_deref();
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.node.map_or(0, |node| node.length);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// Keeps the iteration order, so the first item ends up at the head.
+impl<T> FromIterator<T> for PersistentList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        values
+            .into_iter()
+            .rev()
+            .fold(PersistentList::new(), |list, value| list.push_front(value))
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        PersistentList::new()
+    }
+}
+
+// Cheap: the clone shares every node.
+impl<T> Clone for PersistentList<T> {
+    fn clone(&self) -> Self {
+        PersistentList {
+            head: self.head.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for PersistentList<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> PartialEq for PersistentList<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for PersistentList<T> where T: Eq {}
+
+#[cfg(test)]
+mod persistent_list_tests {
+    use std::rc::Rc;
+
+    use super::PersistentList;
+
+    #[test]
+    fn test_versions_share_tails() {
+        let empty = PersistentList::new();
+        let one = empty.push_front(1);
+        let two = one.push_front(2);
+        let other = one.push_front(3);
+
+        assert!(empty.is_empty());
+        assert_eq!(two.iter().copied().collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), [3, 1]);
+        assert_eq!(two.len(), 2);
+        assert_eq!(two.head(), Some(&2));
+
+        assert!(two.tail().unwrap().ptr_eq(&one));
+        assert!(other.tail().unwrap().ptr_eq(&one));
+        assert!(empty.tail().is_none());
+        assert!(one.tail().unwrap().ptr_eq(&empty));
+
+        drop(one);
+        assert_eq!(two.iter().copied().collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), [3, 1]);
+    }
+
+    #[test]
+    fn test_reverse_and_append() {
+        let left: PersistentList<_> = (1..=3).collect();
+        let right: PersistentList<_> = (4..=5).collect();
+
+        let reversed = left.reverse();
+        assert_eq!(reversed, (1..=3).rev().collect());
+        assert_eq!(left, (1..=3).collect());
+
+        let joined = left.append(&right);
+        assert_eq!(joined, (1..=5).collect());
+        assert_eq!(joined.len(), 5);
+        assert!(joined
+            .tail()
+            .unwrap()
+            .tail()
+            .unwrap()
+            .tail()
+            .unwrap()
+            .ptr_eq(&right));
+        assert_eq!(left.len(), 3);
+
+        assert_eq!(PersistentList::new().append(&right), right);
+        assert_eq!(format!("{right:?}"), "[4, 5]");
+    }
+
+    #[test]
+    fn test_drop_stops_at_shared_nodes() {
+        let shared = Rc::new(());
+        let base: PersistentList<_> = (0..100).map(|_| shared.clone()).collect();
+        let branch = base.push_front(shared.clone()).push_front(shared.clone());
+
+        drop(branch);
+        assert_eq!(Rc::strong_count(&shared), 101);
+        drop(base);
+        assert_eq!(Rc::strong_count(&shared), 1);
+
+        // Long enough that a recursive drop would overflow the stack.
+        let long: PersistentList<_> = (0..500_000).collect();
+        let undo = long.push_front(-1);
+        drop(long);
+        assert_eq!(undo.len(), 500_001);
+    }
+}
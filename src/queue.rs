@@ -0,0 +1,154 @@
+use crate::linked_list::{IntoIter, Iter, LinkedList, LinkedListError};
+
+// A FIFO view of `LinkedList`: items go in at the back and come out at the
+// front, both O(1).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Queue<T>
+where
+    T: Copy + PartialEq,
+{
+    list: LinkedList<T>,
+    limit: Option<usize>,
+}
+
+impl<T> Queue<T>
+where
+    T: Copy + PartialEq,
+{
+    pub fn new() -> Self {
+        Queue {
+            list: LinkedList::new(),
+            limit: None,
+        }
+    }
+
+    // `enqueue` fails with `LinkedListError::Full` once `limit` items are
+    // held.
+    pub fn with_capacity_limit(limit: usize) -> Self {
+        Queue {
+            list: LinkedList::new(),
+            limit: Some(limit),
+        }
+    }
+
+    pub fn capacity_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.length()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.limit.is_some_and(|limit| self.len() >= limit)
+    }
+
+    pub fn enqueue(&mut self, item: T) -> Result<(), LinkedListError> {
+        if let Some(capacity) = self.limit.filter(|_| self.is_full()) {
+            return Err(LinkedListError::Full { capacity });
+        }
+
+        self.list.push_back(item);
+        Ok(())
+    }
+
+    pub fn dequeue(&mut self) -> Result<T, LinkedListError> {
+        self.list.pop_front()
+    }
+
+    // The item `dequeue` would return next.
+    pub fn peek(&self) -> Result<T, LinkedListError> {
+        self.list.front()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    // From the oldest item to the newest.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+}
+
+impl<T> IntoIterator for Queue<T>
+where
+    T: Copy + PartialEq,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T>
+where
+    T: Copy + PartialEq,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T>
+where
+    T: Copy + PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Queue {
+            list: iter.into_iter().collect(),
+            limit: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod queue_tests {
+    use super::Queue;
+    use crate::linked_list::LinkedListError;
+
+    #[test]
+    fn test_enqueue_dequeue_peek() {
+        let mut queue = Queue::new();
+        assert_eq!(queue.dequeue(), Err(LinkedListError::EmptyList));
+
+        queue.enqueue(1).unwrap();
+        queue.enqueue(2).unwrap();
+        queue.enqueue(3).unwrap();
+        assert_eq!(queue.peek(), Ok(1));
+        assert_eq!(queue.iter().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(queue.dequeue(), Ok(1));
+        assert_eq!(queue.len(), 2);
+
+        let queue: Queue<u32> = (1..=4).collect();
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_capacity_limit() {
+        let mut queue = Queue::with_capacity_limit(2);
+        queue.enqueue('a').unwrap();
+        queue.enqueue('b').unwrap();
+        assert!(queue.is_full());
+        assert_eq!(
+            queue.enqueue('c'),
+            Err(LinkedListError::Full { capacity: 2 })
+        );
+
+        assert_eq!(queue.dequeue(), Ok('a'));
+        assert_eq!(queue.enqueue('c'), Ok(()));
+        assert_eq!((&queue).into_iter().collect::<String>(), "bc");
+
+        queue.clear();
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,637 @@
+use std::{
+    fmt,
+    iter::FromIterator,
+    ops::{Bound, RangeBounds},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// Enough for 2^32 entries at the usual one-in-two promotion rate.
+const MAX_LEVEL: usize = 32;
+const HEAD: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipListError {
+    OutOfBounds { index: usize, len: usize },
+}
+
+impl fmt::Display for SkipListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipListError::OutOfBounds { index, len } => {
+                write!(
+                    f,
+                    "index {index} is out of bounds for a skip list of length {len}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SkipListError {}
+
+// `width` is how many positions the link jumps. A link with no `next` runs
+// to the position just past the end, so every width stays meaningful and
+// insert and remove can update them all with the same arithmetic.
+#[derive(Clone, Copy)]
+struct Link {
+    next: Option<usize>,
+    width: usize,
+}
+
+struct Node<E> {
+    entry: Option<E>,
+    links: Vec<Link>,
+}
+
+// The predecessor of the searched position on every level, and that
+// predecessor's position (the head is position 0).
+struct Path {
+    update: [usize; MAX_LEVEL],
+    rank: [usize; MAX_LEVEL],
+}
+
+// Both skip lists share this core. Nodes live in an arena and link to each
+// other by index; removed slots are reused. The head node at index 0 holds no
+// entry and has a link on every level.
+struct Core<E> {
+    nodes: Vec<Node<E>>,
+    free: Vec<usize>,
+    len: usize,
+    rng: StdRng,
+}
+
+impl<E> Core<E> {
+    fn new(rng: StdRng) -> Self {
+        let head = Node {
+            entry: None,
+            links: vec![
+                Link {
+                    next: None,
+                    width: 1,
+                };
+                MAX_LEVEL
+            ],
+        };
+
+        Core {
+            nodes: vec![head],
+            free: Vec::new(),
+            len: 0,
+            rng,
+        }
+    }
+
+    fn entry(&self, node: usize) -> &E {
+        match &self.nodes[node].entry {
+            Some(entry) => entry,
+            None => unreachable!("only the head node has no entry"),
+        }
+    }
+
+    fn entry_mut(&mut self, node: usize) -> &mut E {
+        match &mut self.nodes[node].entry {
+            Some(entry) => entry,
+            None => unreachable!("only the head node has no entry"),
+        }
+    }
+
+    // Walks down from the top level, moving right while `before` says the
+    // next node (given with its position) comes before the target.
+    fn search<F>(&self, mut before: F) -> Path
+    where
+        F: FnMut(&E, usize) -> bool,
+    {
+        let mut path = Path {
+            update: [HEAD; MAX_LEVEL],
+            rank: [0; MAX_LEVEL],
+        };
+        let (mut node, mut rank) = (HEAD, 0);
+
+        for level in (0..MAX_LEVEL).rev() {
+            while let Link {
+                next: Some(next),
+                width,
+            } = self.nodes[node].links[level]
+            {
+                if !before(self.entry(next), rank + width) {
+                    break;
+                }
+                node = next;
+                rank += width;
+            }
+            path.update[level] = node;
+            path.rank[level] = rank;
+        }
+
+        path
+    }
+
+    // The predecessor of index `index`.
+    fn search_index(&self, index: usize) -> Path {
+        self.search(|_, position| position <= index)
+    }
+
+    // The node just after the path, i.e. at the searched position.
+    fn target(&self, path: &Path) -> Option<usize> {
+        self.nodes[path.update[0]].links[0].next
+    }
+
+    fn insert(&mut self, path: &Path, entry: E) -> usize {
+        let height = self.random_height();
+        let node = Node {
+            entry: Some(entry),
+            links: Vec::with_capacity(height),
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        for level in 0..MAX_LEVEL {
+            let update = path.update[level];
+            if level < height {
+                let skipped = path.rank[0] - path.rank[level];
+                let old = self.nodes[update].links[level];
+                self.nodes[index].links.push(Link {
+                    next: old.next,
+                    width: old.width - skipped,
+                });
+                self.nodes[update].links[level] = Link {
+                    next: Some(index),
+                    width: skipped + 1,
+                };
+            } else {
+                self.nodes[update].links[level].width += 1;
+            }
+        }
+
+        self.len += 1;
+        index
+    }
+
+    // Unlinks the node at the path's target, which must exist.
+    fn remove(&mut self, path: &Path) -> E {
+        let Some(index) = self.target(path) else {
+            unreachable!("the caller checked there is a node to remove");
+        };
+        let links = std::mem::take(&mut self.nodes[index].links);
+
+        for level in 0..MAX_LEVEL {
+            let update = &mut self.nodes[path.update[level]].links[level];
+            match links.get(level) {
+                Some(link) => {
+                    *update = Link {
+                        next: link.next,
+                        width: update.width + link.width - 1,
+                    }
+                }
+                None => update.width -= 1,
+            }
+        }
+
+        self.len -= 1;
+        self.free.push(index);
+        match self.nodes[index].entry.take() {
+            Some(entry) => entry,
+            None => unreachable!("only the head node has no entry"),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.free.clear();
+        self.len = 0;
+        for link in &mut self.nodes[HEAD].links {
+            *link = Link {
+                next: None,
+                width: 1,
+            };
+        }
+    }
+
+    // Entries in order, starting from the node after `from`.
+    fn iter_from(&self, from: usize) -> impl Iterator<Item = &E> + '_ {
+        let mut node = self.nodes[from].links[0].next;
+        std::iter::from_fn(move || {
+            let current = node?;
+            node = self.nodes[current].links[0].next;
+            Some(self.entry(current))
+        })
+    }
+
+    // Each extra level with probability one half.
+    fn random_height(&mut self) -> usize {
+        let mut height = 1;
+        while height < MAX_LEVEL && self.rng.gen::<bool>() {
+            height += 1;
+        }
+        height
+    }
+}
+
+// An ordered map with expected O(log n) insert, lookup and removal. The span
+// widths it keeps also give O(log n) access by rank.
+pub struct SkipList<K, V>
+where
+    K: Ord,
+{
+    core: Core<(K, V)>,
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        SkipList {
+            core: Core::new(StdRng::from_entropy()),
+        }
+    }
+
+    // The same seed and the same operations build the same levels.
+    pub fn with_seed(seed: u64) -> Self {
+        SkipList {
+            core: Core::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.core.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.core.len == 0
+    }
+
+    // Returns the old value when the key was already present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let path = self.lower_bound(&key);
+        if let Some(node) = self.find(&path, &key) {
+            let (_, old) = self.core.entry_mut(node);
+            return Some(std::mem::replace(old, value));
+        }
+
+        self.core.insert(&path, (key, value));
+        None
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let path = self.lower_bound(key);
+        let node = self.find(&path, key)?;
+        Some(&self.core.entry(node).1)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let path = self.lower_bound(key);
+        let node = self.find(&path, key)?;
+        Some(&mut self.core.entry_mut(node).1)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let path = self.lower_bound(key);
+        self.find(&path, key)?;
+        Some(self.core.remove(&path).1)
+    }
+
+    // The entry with the given rank in key order.
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        let path = self.core.search_index(index);
+        let (key, value) = self.core.entry(self.core.target(&path)?);
+        Some((key, value))
+    }
+
+    // How many keys are smaller than `key`, whether or not it is present.
+    pub fn rank(&self, key: &K) -> usize {
+        self.lower_bound(key).rank[0]
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.get_index(0)
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.get_index(self.core.len.checked_sub(1)?)
+    }
+
+    pub fn range<'a, R>(&'a self, range: R) -> impl Iterator<Item = (&'a K, &'a V)> + 'a
+    where
+        R: RangeBounds<K> + 'a,
+    {
+        let path = match range.start_bound() {
+            Bound::Included(start) => self.core.search(|(key, _), _| key < start),
+            Bound::Excluded(start) => self.core.search(|(key, _), _| key <= start),
+            Bound::Unbounded => self.core.search(|_, _| false),
+        };
+
+        self.core
+            .iter_from(path.update[0])
+            .take_while(move |(key, _)| match range.end_bound() {
+                Bound::Included(end) => key <= end,
+                Bound::Excluded(end) => key < end,
+                Bound::Unbounded => true,
+            })
+            .map(|(key, value)| (key, value))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.core.iter_from(HEAD).map(|(key, value)| (key, value))
+    }
+
+    pub fn clear(&mut self) {
+        self.core.clear();
+    }
+
+    fn lower_bound(&self, key: &K) -> Path {
+        self.core.search(|(other, _), _| other < key)
+    }
+
+    fn find(&self, path: &Path, key: &K) -> Option<usize> {
+        let node = self.core.target(path)?;
+        (self.core.entry(node).0 == *key).then_some(node)
+    }
+}
+
+impl<K, V> Default for SkipList<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        SkipList::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for SkipList<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut list = SkipList::new();
+        for (key, value) in iter {
+            list.insert(key, value);
+        }
+        list
+    }
+}
+
+impl<K, V> fmt::Debug for SkipList<K, V>
+where
+    K: Ord + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// A sequence with expected O(log n) get, insert and remove at any position,
+// found by summing span widths instead of walking node by node.
+pub struct IndexedSkipList<T> {
+    core: Core<T>,
+}
+
+impl<T> IndexedSkipList<T> {
+    pub fn new() -> Self {
+        IndexedSkipList {
+            core: Core::new(StdRng::from_entropy()),
+        }
+    }
+
+    // The same seed and the same operations build the same levels.
+    pub fn with_seed(seed: u64) -> Self {
+        IndexedSkipList {
+            core: Core::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.core.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.core.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Result<&T, SkipListError> {
+        let node = self.node_at(index)?;
+        Ok(self.core.entry(node))
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Result<&mut T, SkipListError> {
+        let node = self.node_at(index)?;
+        Ok(self.core.entry_mut(node))
+    }
+
+    // `index` may be the length, which appends.
+    pub fn insert(&mut self, index: usize, item: T) -> Result<(), SkipListError> {
+        if index > self.core.len {
+            return Err(self.out_of_bounds(index));
+        }
+
+        let path = self.core.search_index(index);
+        self.core.insert(&path, item);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, SkipListError> {
+        if index >= self.core.len {
+            return Err(self.out_of_bounds(index));
+        }
+
+        let path = self.core.search_index(index);
+        Ok(self.core.remove(&path))
+    }
+
+    pub fn push_front(&mut self, item: T) {
+        let path = self.core.search_index(0);
+        self.core.insert(&path, item);
+    }
+
+    pub fn push_back(&mut self, item: T) {
+        let path = self.core.search_index(self.core.len);
+        self.core.insert(&path, item);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.core.iter_from(HEAD)
+    }
+
+    pub fn clear(&mut self) {
+        self.core.clear();
+    }
+
+    fn node_at(&self, index: usize) -> Result<usize, SkipListError> {
+        let path = self.core.search_index(index);
+        self.core
+            .target(&path)
+            .ok_or_else(|| self.out_of_bounds(index))
+    }
+
+    fn out_of_bounds(&self, index: usize) -> SkipListError {
+        SkipListError::OutOfBounds {
+            index,
+            len: self.core.len,
+        }
+    }
+}
+
+impl<T> Default for IndexedSkipList<T> {
+    fn default() -> Self {
+        IndexedSkipList::new()
+    }
+}
+
+impl<T> FromIterator<T> for IndexedSkipList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = IndexedSkipList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for IndexedSkipList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> fmt::Debug for IndexedSkipList<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod skip_list_tests {
+    use std::collections::BTreeMap;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{Core, IndexedSkipList, SkipList, SkipListError, HEAD, MAX_LEVEL};
+
+    // Every link's width must match the distance to the node it points at,
+    // or to one past the end.
+    fn check_widths<E>(core: &Core<E>) {
+        let mut positions = vec![None; core.nodes.len()];
+        positions[HEAD] = Some(0);
+        let mut node = core.nodes[HEAD].links[0].next;
+        let mut position = 0;
+        while let Some(current) = node {
+            position += 1;
+            positions[current] = Some(position);
+            node = core.nodes[current].links[0].next;
+        }
+        assert_eq!(position, core.len);
+
+        for (index, node) in core.nodes.iter().enumerate() {
+            let Some(start) = positions[index] else {
+                continue;
+            };
+            for link in &node.links {
+                let end = link
+                    .next
+                    .map_or(core.len + 1, |next| positions[next].unwrap());
+                assert_eq!(link.width, end - start);
+            }
+        }
+        assert_eq!(core.nodes[HEAD].links.len(), MAX_LEVEL);
+    }
+
+    #[test]
+    fn test_map_against_btree_map() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut list = SkipList::with_seed(3);
+        let mut model = BTreeMap::new();
+
+        for _ in 0..5_000 {
+            let key = rng.gen_range(0..500);
+            match rng.gen_range(0..3) {
+                0 | 1 => assert_eq!(list.insert(key, key * 2), model.insert(key, key * 2)),
+                _ => assert_eq!(list.remove(&key), model.remove(&key)),
+            }
+        }
+        check_widths(&list.core);
+
+        assert_eq!(list.len(), model.len());
+        assert!(list.iter().eq(model.iter()));
+        assert!(list.range(100..=200).eq(model.range(100..=200)));
+        assert!(list.range(..50).eq(model.range(..50)));
+        assert_eq!(list.first(), model.iter().next());
+        assert_eq!(list.last(), model.iter().next_back());
+
+        for (rank, (key, value)) in model.iter().enumerate() {
+            assert_eq!(list.get(key), Some(value));
+            assert_eq!(list.get_index(rank), Some((key, value)));
+            assert_eq!(list.rank(key), rank);
+        }
+        assert_eq!(list.get_index(model.len()), None);
+
+        *list.get_mut(model.keys().next().unwrap()).unwrap() = -1;
+        assert_eq!(list.first().map(|(_, value)| *value), Some(-1));
+        list.clear();
+        assert!(list.is_empty());
+        check_widths(&list.core);
+    }
+
+    #[test]
+    fn test_sequence_against_vec() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let mut list = IndexedSkipList::with_seed(4);
+        let mut model = Vec::new();
+
+        for step in 0..5_000 {
+            let index = rng.gen_range(0..=model.len());
+            match rng.gen_range(0..3) {
+                0 | 1 => {
+                    list.insert(index, step).unwrap();
+                    model.insert(index, step);
+                }
+                _ if index < model.len() => assert_eq!(list.remove(index), Ok(model.remove(index))),
+                _ => assert!(list.remove(index).is_err()),
+            }
+        }
+        check_widths(&list.core);
+
+        assert!(list.iter().eq(model.iter()));
+        for (index, value) in model.iter().enumerate() {
+            assert_eq!(list.get(index), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_sequence_ends_and_errors() {
+        let mut list: IndexedSkipList<_> = (1..=3).collect();
+        list.push_front(0);
+        list.push_back(4);
+        *list.get_mut(2).unwrap() = 20;
+        assert_eq!(format!("{list:?}"), "[0, 1, 20, 3, 4]");
+
+        assert_eq!(
+            list.get(5),
+            Err(SkipListError::OutOfBounds { index: 5, len: 5 })
+        );
+        assert_eq!(
+            list.insert(6, 0),
+            Err(SkipListError::OutOfBounds { index: 6, len: 5 })
+        );
+        assert_eq!(
+            SkipListError::OutOfBounds { index: 6, len: 5 }.to_string(),
+            "index 6 is out of bounds for a skip list of length 5"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::linked_list::{IntoIter, Iter, LinkedList, LinkedListError};
+
+// A LIFO view of `LinkedList` that only exposes the top. The top is the
+// front of the list, so every operation is O(1).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Stack<T>
+where
+    T: Copy + PartialEq,
+{
+    list: LinkedList<T>,
+    limit: Option<usize>,
+}
+
+impl<T> Stack<T>
+where
+    T: Copy + PartialEq,
+{
+    pub fn new() -> Self {
+        Stack {
+            list: LinkedList::new(),
+            limit: None,
+        }
+    }
+
+    // `push` fails with `LinkedListError::Full` once `limit` items are held.
+    pub fn with_capacity_limit(limit: usize) -> Self {
+        Stack {
+            list: LinkedList::new(),
+            limit: Some(limit),
+        }
+    }
+
+    pub fn capacity_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.length()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.limit.is_some_and(|limit| self.len() >= limit)
+    }
+
+    pub fn push(&mut self, item: T) -> Result<(), LinkedListError> {
+        if let Some(capacity) = self.limit.filter(|_| self.is_full()) {
+            return Err(LinkedListError::Full { capacity });
+        }
+
+        self.list.push_front(item);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Result<T, LinkedListError> {
+        self.list.pop_front()
+    }
+
+    pub fn peek(&self) -> Result<T, LinkedListError> {
+        self.list.front()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    // From the top down, the order `pop` would return them in.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+}
+
+impl<T> IntoIterator for Stack<T>
+where
+    T: Copy + PartialEq,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T>
+where
+    T: Copy + PartialEq,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Pushes in iteration order, so the last item ends up on top.
+impl<T> FromIterator<T> for Stack<T>
+where
+    T: Copy + PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Stack::new();
+        for item in iter {
+            stack.list.push_front(item);
+        }
+        stack
+    }
+}
+
+#[cfg(test)]
+mod stack_tests {
+    use super::Stack;
+    use crate::linked_list::LinkedListError;
+
+    #[test]
+    fn test_push_pop_peek() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.pop(), Err(LinkedListError::EmptyList));
+
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        stack.push(3).unwrap();
+        assert_eq!(stack.peek(), Ok(3));
+        assert_eq!(stack.iter().collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(stack.pop(), Ok(3));
+        assert_eq!(stack.len(), 2);
+
+        let stack: Stack<u32> = (1..=4).collect();
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_capacity_limit() {
+        let mut stack = Stack::with_capacity_limit(2);
+        assert_eq!(stack.capacity_limit(), Some(2));
+        stack.push('a').unwrap();
+        stack.push('b').unwrap();
+        assert!(stack.is_full());
+        assert_eq!(stack.push('c'), Err(LinkedListError::Full { capacity: 2 }));
+
+        stack.pop().unwrap();
+        assert_eq!(stack.push('c'), Ok(()));
+        assert_eq!(stack.iter().collect::<String>(), "ca");
+        assert!(!Stack::<u8>::new().is_full());
+    }
+}
@@ -0,0 +1,711 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    ops::RangeBounds,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::traits::{Collection, Deque, OrderedMap};
+
+// A trace is plain text with one operation per line, for example
+//
+//     # comments and blank lines are skipped
+//     push_back 42
+//     pop_front
+//     insert 7 700
+//     remove 7
+//
+// Deque traces use `push_front`, `push_back`, `pop_front`, `pop_back`, `front`
+// and `back`; map traces use `insert`, `get`, `remove`, `first` and `last`.
+// `len` and `clear` belong to both.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op<K, V = K> {
+    PushFront(V),
+    PushBack(V),
+    PopFront,
+    PopBack,
+    Front,
+    Back,
+    Insert(K, V),
+    Get(K),
+    Remove(K),
+    First,
+    Last,
+    Len,
+    Clear,
+}
+
+impl<K, V> Op<K, V> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Op::PushFront(_) => "push_front",
+            Op::PushBack(_) => "push_back",
+            Op::PopFront => "pop_front",
+            Op::PopBack => "pop_back",
+            Op::Front => "front",
+            Op::Back => "back",
+            Op::Insert(..) => "insert",
+            Op::Get(_) => "get",
+            Op::Remove(_) => "remove",
+            Op::First => "first",
+            Op::Last => "last",
+            Op::Len => "len",
+            Op::Clear => "clear",
+        }
+    }
+
+    pub fn is_deque_op(&self) -> bool {
+        matches!(
+            self,
+            Op::PushFront(_)
+                | Op::PushBack(_)
+                | Op::PopFront
+                | Op::PopBack
+                | Op::Front
+                | Op::Back
+                | Op::Len
+                | Op::Clear
+        )
+    }
+
+    pub fn is_map_op(&self) -> bool {
+        matches!(
+            self,
+            Op::Insert(..)
+                | Op::Get(_)
+                | Op::Remove(_)
+                | Op::First
+                | Op::Last
+                | Op::Len
+                | Op::Clear
+        )
+    }
+}
+
+impl<K, V> fmt::Display for Op<K, V>
+where
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())?;
+        match self {
+            Op::PushFront(value) | Op::PushBack(value) => write!(f, " {value}"),
+            Op::Insert(key, value) => write!(f, " {key} {value}"),
+            Op::Get(key) | Op::Remove(key) => write!(f, " {key}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceError {
+    UnknownOperation { line: usize, name: String },
+    MissingArgument { line: usize },
+    InvalidArgument { line: usize, value: String },
+    UnexpectedArgument { line: usize, value: String },
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::UnknownOperation { line, name } => {
+                write!(f, "line {line}: unknown operation `{name}`")
+            }
+            TraceError::MissingArgument { line } => write!(f, "line {line}: missing argument"),
+            TraceError::InvalidArgument { line, value } => {
+                write!(f, "line {line}: invalid argument `{value}`")
+            }
+            TraceError::UnexpectedArgument { line, value } => {
+                write!(f, "line {line}: unexpected argument `{value}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<K, V = K> {
+    pub ops: Vec<Op<K, V>>,
+}
+
+impl<K, V> Trace<K, V> {
+    pub fn new() -> Self {
+        Trace { ops: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    pub fn is_deque_trace(&self) -> bool {
+        self.ops.iter().all(Op::is_deque_op)
+    }
+
+    pub fn is_map_trace(&self) -> bool {
+        self.ops.iter().all(Op::is_map_op)
+    }
+}
+
+impl<K, V> Default for Trace<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Display for Trace<K, V>
+where
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for op in &self.ops {
+            writeln!(f, "{op}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<K, V> FromStr for Trace<K, V>
+where
+    K: FromStr,
+    V: FromStr,
+{
+    type Err = TraceError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut trace = Trace::new();
+
+        for (index, text) in input.lines().enumerate() {
+            let line = index + 1;
+            let text = text.split('#').next().unwrap_or_default();
+            let mut words = text.split_whitespace();
+            let Some(name) = words.next() else {
+                continue;
+            };
+
+            let mut arg = || words.next().ok_or(TraceError::MissingArgument { line });
+
+            let op = match name {
+                "push_front" => Op::PushFront(parse_arg(line, arg()?)?),
+                "push_back" => Op::PushBack(parse_arg(line, arg()?)?),
+                "pop_front" => Op::PopFront,
+                "pop_back" => Op::PopBack,
+                "front" => Op::Front,
+                "back" => Op::Back,
+                "insert" => {
+                    let key = parse_arg(line, arg()?)?;
+                    Op::Insert(key, parse_arg(line, arg()?)?)
+                }
+                "get" => Op::Get(parse_arg(line, arg()?)?),
+                "remove" => Op::Remove(parse_arg(line, arg()?)?),
+                "first" => Op::First,
+                "last" => Op::Last,
+                "len" => Op::Len,
+                "clear" => Op::Clear,
+                _ => {
+                    return Err(TraceError::UnknownOperation {
+                        line,
+                        name: name.to_string(),
+                    })
+                }
+            };
+
+            if let Some(value) = words.next() {
+                return Err(TraceError::UnexpectedArgument {
+                    line,
+                    value: value.to_string(),
+                });
+            }
+            trace.ops.push(op);
+        }
+
+        Ok(trace)
+    }
+}
+
+fn parse_arg<T: FromStr>(line: usize, word: &str) -> Result<T, TraceError> {
+    word.parse().map_err(|_| TraceError::InvalidArgument {
+        line,
+        value: word.to_string(),
+    })
+}
+
+// What one operation returned, so a replay can be compared step by step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<K, V = K> {
+    Done,
+    Len(usize),
+    Value(Option<V>),
+    Entry(Option<(K, V)>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError<K, V = K> {
+    // `step` counts operations from 1, skipping comments and blank lines.
+    Unsupported {
+        step: usize,
+        op: Op<K, V>,
+    },
+    Mismatch {
+        step: usize,
+        op: Op<K, V>,
+        expected: Outcome<K, V>,
+        actual: Outcome<K, V>,
+    },
+}
+
+impl<K, V> fmt::Display for ReplayError<K, V>
+where
+    K: fmt::Display + fmt::Debug,
+    V: fmt::Display + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Unsupported { step, op } => {
+                write!(f, "step {step}: `{op}` does not apply to this container")
+            }
+            ReplayError::Mismatch {
+                step,
+                op,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "step {step}: `{op}` returned {actual:?}, the model returned {expected:?}"
+            ),
+        }
+    }
+}
+
+impl<K, V> std::error::Error for ReplayError<K, V>
+where
+    K: fmt::Display + fmt::Debug,
+    V: fmt::Display + fmt::Debug,
+{
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replay {
+    pub operations: usize,
+    // Time spent in the container under test; the model check is not counted.
+    pub elapsed: Duration,
+}
+
+impl<T> Trace<T, T>
+where
+    T: Clone + PartialEq,
+{
+    // Panics on a map operation; check `is_deque_trace` first.
+    pub fn apply_deque<D: Deque<T>>(&self, deque: &mut D) -> Vec<Outcome<T, T>> {
+        self.ops
+            .iter()
+            .map(|op| match op {
+                Op::PushFront(value) => {
+                    deque.push_front(value.clone());
+                    Outcome::Done
+                }
+                Op::PushBack(value) => {
+                    deque.push_back(value.clone());
+                    Outcome::Done
+                }
+                Op::PopFront => Outcome::Value(deque.pop_front()),
+                Op::PopBack => Outcome::Value(deque.pop_back()),
+                Op::Front => Outcome::Value(deque.front()),
+                Op::Back => Outcome::Value(deque.back()),
+                Op::Len => Outcome::Len(deque.len()),
+                Op::Clear => {
+                    deque.clear();
+                    Outcome::Done
+                }
+                _ => panic!("`{}` is not a deque operation", op.name()),
+            })
+            .collect()
+    }
+
+    pub fn check_deque(&self, outcomes: &[Outcome<T, T>]) -> Result<(), ReplayError<T, T>> {
+        let expected = self.apply_deque(&mut VecDeque::new());
+        compare(&self.ops, &expected, outcomes)
+    }
+}
+
+impl<K, V> Trace<K, V>
+where
+    K: Ord + Clone,
+    V: Clone + PartialEq,
+{
+    // Panics on a deque operation; check `is_map_trace` first.
+    pub fn apply_map<M: OrderedMap<K, V>>(&self, map: &mut M) -> Vec<Outcome<K, V>> {
+        self.ops
+            .iter()
+            .map(|op| match op {
+                Op::Insert(key, value) => Outcome::Value(map.insert(key.clone(), value.clone())),
+                Op::Get(key) => Outcome::Value(map.get(key)),
+                Op::Remove(key) => Outcome::Value(map.remove(key)),
+                Op::First => Outcome::Entry(map.first()),
+                Op::Last => Outcome::Entry(map.last()),
+                Op::Len => Outcome::Len(map.len()),
+                Op::Clear => {
+                    map.clear();
+                    Outcome::Done
+                }
+                _ => panic!("`{}` is not a map operation", op.name()),
+            })
+            .collect()
+    }
+
+    pub fn check_map(&self, outcomes: &[Outcome<K, V>]) -> Result<(), ReplayError<K, V>> {
+        let expected = self.apply_map(&mut BTreeMap::new());
+        compare(&self.ops, &expected, outcomes)
+    }
+}
+
+fn compare<K, V>(
+    ops: &[Op<K, V>],
+    expected: &[Outcome<K, V>],
+    actual: &[Outcome<K, V>],
+) -> Result<(), ReplayError<K, V>>
+where
+    K: Clone + PartialEq,
+    V: Clone + PartialEq,
+{
+    let steps = ops.iter().zip(expected).zip(actual).enumerate();
+    for (index, ((op, expected), actual)) in steps {
+        if expected != actual {
+            return Err(ReplayError::Mismatch {
+                step: index + 1,
+                op: op.clone(),
+                expected: expected.clone(),
+                actual: actual.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn unsupported<K, V, F>(trace: &Trace<K, V>, supported: F) -> Option<ReplayError<K, V>>
+where
+    K: Clone,
+    V: Clone,
+    F: Fn(&Op<K, V>) -> bool,
+{
+    let index = trace.ops.iter().position(|op| !supported(op))?;
+    Some(ReplayError::Unsupported {
+        step: index + 1,
+        op: trace.ops[index].clone(),
+    })
+}
+
+pub fn replay_deque<T, D>(trace: &Trace<T, T>, deque: &mut D) -> Result<Replay, ReplayError<T, T>>
+where
+    T: Clone + PartialEq,
+    D: Deque<T>,
+{
+    if let Some(err) = unsupported(trace, Op::is_deque_op) {
+        return Err(err);
+    }
+
+    let start = Instant::now();
+    let outcomes = trace.apply_deque(deque);
+    let elapsed = start.elapsed();
+
+    trace.check_deque(&outcomes)?;
+    Ok(Replay {
+        operations: trace.len(),
+        elapsed,
+    })
+}
+
+pub fn replay_map<K, V, M>(trace: &Trace<K, V>, map: &mut M) -> Result<Replay, ReplayError<K, V>>
+where
+    K: Ord + Clone,
+    V: Clone + PartialEq,
+    M: OrderedMap<K, V>,
+{
+    if let Some(err) = unsupported(trace, Op::is_map_op) {
+        return Err(err);
+    }
+
+    let start = Instant::now();
+    let outcomes = trace.apply_map(map);
+    let elapsed = start.elapsed();
+
+    trace.check_map(&outcomes)?;
+    Ok(Replay {
+        operations: trace.len(),
+        elapsed,
+    })
+}
+
+// Wraps a container and writes every call made through `Deque` or
+// `OrderedMap` into a trace. `len`, `contains_key` and range scans pass
+// through unrecorded.
+#[derive(Debug)]
+pub struct Recorder<C, K, V = K> {
+    inner: C,
+    trace: RefCell<Trace<K, V>>,
+}
+
+impl<C, K, V> Recorder<C, K, V> {
+    pub fn new(inner: C) -> Self {
+        Recorder {
+            inner,
+            trace: RefCell::new(Trace::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn into_parts(self) -> (C, Trace<K, V>) {
+        (self.inner, self.trace.into_inner())
+    }
+
+    fn record(&self, op: Op<K, V>) {
+        self.trace.borrow_mut().ops.push(op);
+    }
+}
+
+impl<C, K, V> Recorder<C, K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    pub fn trace(&self) -> Trace<K, V> {
+        self.trace.borrow().clone()
+    }
+}
+
+impl<C, K, V> Default for Recorder<C, K, V>
+where
+    C: Default,
+{
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C, K, V> Collection for Recorder<C, K, V>
+where
+    C: Collection,
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.record(Op::Clear);
+        self.inner.clear();
+    }
+}
+
+impl<C, T> Deque<T> for Recorder<C, T, T>
+where
+    C: Deque<T>,
+    T: Clone,
+{
+    fn push_front(&mut self, value: T) {
+        self.record(Op::PushFront(value.clone()));
+        self.inner.push_front(value);
+    }
+
+    fn push_back(&mut self, value: T) {
+        self.record(Op::PushBack(value.clone()));
+        self.inner.push_back(value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.record(Op::PopFront);
+        self.inner.pop_front()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        self.record(Op::PopBack);
+        self.inner.pop_back()
+    }
+
+    fn front(&self) -> Option<T> {
+        self.record(Op::Front);
+        self.inner.front()
+    }
+
+    fn back(&self) -> Option<T> {
+        self.record(Op::Back);
+        self.inner.back()
+    }
+}
+
+impl<C, K, V> OrderedMap<K, V> for Recorder<C, K, V>
+where
+    C: OrderedMap<K, V>,
+    K: Clone,
+    V: Clone,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.record(Op::Insert(key.clone(), value.clone()));
+        self.inner.insert(key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.record(Op::Get(key.clone()));
+        self.inner.get(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.record(Op::Remove(key.clone()));
+        self.inner.remove(key)
+    }
+
+    fn range<R>(&self, range: R) -> impl Iterator<Item = (K, V)>
+    where
+        R: RangeBounds<K>,
+    {
+        self.inner.range(range)
+    }
+
+    fn first(&self) -> Option<(K, V)> {
+        self.record(Op::First);
+        self.inner.first()
+    }
+
+    fn last(&self) -> Option<(K, V)> {
+        self.record(Op::Last);
+        self.inner.last()
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.inner.contains_key(key)
+    }
+}
+
+#[cfg(all(test, feature = "linked-list", feature = "avl"))]
+mod trace_tests {
+    use std::collections::VecDeque;
+
+    use super::{replay_deque, replay_map, Op, Outcome, Recorder, ReplayError, Trace, TraceError};
+    use crate::{
+        avl_tree::AVLTree,
+        linked_list::LinkedList,
+        traits::{Collection, Deque, OrderedMap},
+    };
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = "# warm up\npush_back 42\n\n  pop_front  # trailing comment\ninsert 7 700\n";
+        let trace: Trace<u64> = text.parse().unwrap();
+
+        assert_eq!(
+            trace.ops,
+            [Op::PushBack(42), Op::PopFront, Op::Insert(7, 700)]
+        );
+        assert_eq!(trace.to_string(), "push_back 42\npop_front\ninsert 7 700\n");
+        assert_eq!(trace.to_string().parse::<Trace<u64>>(), Ok(trace));
+
+        assert_eq!(
+            "len\npush 1".parse::<Trace<u64>>(),
+            Err(TraceError::UnknownOperation {
+                line: 2,
+                name: String::from("push")
+            })
+        );
+        assert_eq!(
+            "get".parse::<Trace<u64>>(),
+            Err(TraceError::MissingArgument { line: 1 })
+        );
+        assert_eq!(
+            "remove x".parse::<Trace<u64>>(),
+            Err(TraceError::InvalidArgument {
+                line: 1,
+                value: String::from("x")
+            })
+        );
+        assert_eq!(
+            "pop_back 3".parse::<Trace<u64>>(),
+            Err(TraceError::UnexpectedArgument {
+                line: 1,
+                value: String::from("3")
+            })
+        );
+    }
+
+    #[test]
+    fn test_record_and_replay_deque() {
+        let mut recorder: Recorder<LinkedList<u32>, u32> = Recorder::default();
+        recorder.push_back(1);
+        recorder.push_front(2);
+        recorder.front();
+        recorder.pop_back();
+        recorder.clear();
+        recorder.pop_front();
+
+        let (list, trace) = recorder.into_parts();
+        assert!(list.is_empty());
+        assert_eq!(trace.len(), 6);
+        assert!(trace.is_deque_trace());
+
+        let replay = replay_deque(&trace, &mut LinkedList::new()).unwrap();
+        assert_eq!(replay.operations, 6);
+        assert!(replay_deque(&trace, &mut VecDeque::new()).is_ok());
+
+        // A container that starts out non-empty diverges from the model.
+        let trace: Trace<u32> = "push_back 1\npop_front\nlen".parse().unwrap();
+        assert_eq!(
+            replay_deque(&trace, &mut VecDeque::from([9])),
+            Err(ReplayError::Mismatch {
+                step: 2,
+                op: Op::PopFront,
+                expected: Outcome::Value(Some(1)),
+                actual: Outcome::Value(Some(9)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_record_and_replay_map() {
+        let mut recorder: Recorder<AVLTree<u32, char>, u32, char> = Recorder::default();
+        recorder.insert(3, 'c');
+        recorder.insert(1, 'a');
+        recorder.insert(3, 'C');
+        recorder.get(&3);
+        recorder.remove(&1);
+        recorder.first();
+
+        let trace = recorder.trace();
+        assert!(trace.is_map_trace());
+        assert!(replay_map(&trace, &mut AVLTree::new()).is_ok());
+
+        let mut stale = AVLTree::new();
+        stale.insert(3, 'x').unwrap();
+        assert_eq!(
+            replay_map(&trace, &mut stale),
+            Err(ReplayError::Mismatch {
+                step: 1,
+                op: Op::Insert(3, 'c'),
+                expected: Outcome::Value(None),
+                actual: Outcome::Value(Some('x')),
+            })
+        );
+        assert_eq!(
+            replay_deque(
+                &"push_back 1\nget 1".parse().unwrap(),
+                &mut LinkedList::new()
+            ),
+            Err(ReplayError::Unsupported {
+                step: 2,
+                op: Op::Get(1)
+            })
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use std::{
+    collections::{self, BTreeMap, VecDeque},
+    ops::RangeBounds,
+};
+
+#[cfg(feature = "avl")]
+use crate::avl_tree::{AVLTree, AVLTreeError};
+#[cfg(feature = "linked-list")]
+use crate::linked_list::LinkedList;
+#[cfg(feature = "linked-list")]
+use crate::unrolled_list::UnrolledList;
+
+// Our containers sit behind `Rc<RefCell<_>>` and cannot hand out references,
+// so every accessor here returns owned values and the std impls clone.
+
+pub trait Collection {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clear(&mut self);
+}
+
+pub trait Deque<T>: Collection {
+    fn push_front(&mut self, value: T);
+    fn push_back(&mut self, value: T);
+    fn pop_front(&mut self) -> Option<T>;
+    fn pop_back(&mut self) -> Option<T>;
+    fn front(&self) -> Option<T>;
+    fn back(&self) -> Option<T>;
+}
+
+pub trait OrderedMap<K, V>: Collection {
+    // Overwrites an existing entry and returns its old value, like `BTreeMap`.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+    fn get(&self, key: &K) -> Option<V>;
+    fn remove(&mut self, key: &K) -> Option<V>;
+    fn range<R>(&self, range: R) -> impl Iterator<Item = (K, V)>
+    where
+        R: RangeBounds<K>;
+    fn first(&self) -> Option<(K, V)>;
+    fn last(&self) -> Option<(K, V)>;
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+#[cfg(feature = "linked-list")]
+impl<T> Collection for LinkedList<T>
+where
+    T: Copy + PartialEq,
+{
+    fn len(&self) -> usize {
+        self.length()
+    }
+
+    fn is_empty(&self) -> bool {
+        LinkedList::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        LinkedList::clear(self);
+    }
+}
+
+#[cfg(feature = "linked-list")]
+impl<T> Deque<T> for LinkedList<T>
+where
+    T: Copy + PartialEq,
+{
+    fn push_front(&mut self, value: T) {
+        LinkedList::push_front(self, value);
+    }
+
+    fn push_back(&mut self, value: T) {
+        LinkedList::push_back(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self).ok()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self).ok()
+    }
+
+    fn front(&self) -> Option<T> {
+        LinkedList::front(self).ok()
+    }
+
+    fn back(&self) -> Option<T> {
+        LinkedList::back(self).ok()
+    }
+}
+
+#[cfg(feature = "linked-list")]
+impl<T, const B: usize> Collection for UnrolledList<T, B>
+where
+    T: Copy + PartialEq,
+{
+    fn len(&self) -> usize {
+        self.length()
+    }
+
+    fn is_empty(&self) -> bool {
+        UnrolledList::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        UnrolledList::clear(self);
+    }
+}
+
+#[cfg(feature = "linked-list")]
+impl<T, const B: usize> Deque<T> for UnrolledList<T, B>
+where
+    T: Copy + PartialEq,
+{
+    fn push_front(&mut self, value: T) {
+        UnrolledList::push_front(self, value);
+    }
+
+    fn push_back(&mut self, value: T) {
+        UnrolledList::push_back(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        UnrolledList::pop_front(self).ok()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        UnrolledList::pop_back(self).ok()
+    }
+
+    fn front(&self) -> Option<T> {
+        UnrolledList::front(self).ok()
+    }
+
+    fn back(&self) -> Option<T> {
+        UnrolledList::back(self).ok()
+    }
+}
+
+#[cfg(feature = "avl")]
+impl<K, V> Collection for AVLTree<K, V>
+where
+    K: PartialEq + Ord,
+{
+    fn len(&self) -> usize {
+        self.size()
+    }
+
+    fn is_empty(&self) -> bool {
+        AVLTree::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        AVLTree::clear(self);
+    }
+}
+
+#[cfg(feature = "avl")]
+impl<K, V> OrderedMap<K, V> for AVLTree<K, V>
+where
+    K: PartialEq + Ord + Clone,
+    V: Clone,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match AVLTree::insert(self, key, value) {
+            Ok(()) => None,
+            Err(AVLTreeError::AlreadyExists { key, value }) => self.replace(key, value).ok(),
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        AVLTree::get(self, key.clone()).ok()
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        AVLTree::remove(self, key.clone()).ok()
+    }
+
+    fn range<R>(&self, range: R) -> impl Iterator<Item = (K, V)>
+    where
+        R: RangeBounds<K>,
+    {
+        AVLTree::range(self, range)
+    }
+
+    fn first(&self) -> Option<(K, V)> {
+        AVLTree::first(self).ok()
+    }
+
+    fn last(&self) -> Option<(K, V)> {
+        AVLTree::last(self).ok()
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        AVLTree::contains_key(self, key)
+    }
+}
+
+impl<T> Collection for VecDeque<T> {
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self);
+    }
+}
+
+impl<T> Deque<T> for VecDeque<T>
+where
+    T: Clone,
+{
+    fn push_front(&mut self, value: T) {
+        VecDeque::push_front(self, value);
+    }
+
+    fn push_back(&mut self, value: T) {
+        VecDeque::push_back(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        VecDeque::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        VecDeque::pop_back(self)
+    }
+
+    fn front(&self) -> Option<T> {
+        VecDeque::front(self).cloned()
+    }
+
+    fn back(&self) -> Option<T> {
+        VecDeque::back(self).cloned()
+    }
+}
+
+impl<T> Collection for collections::LinkedList<T> {
+    fn len(&self) -> usize {
+        collections::LinkedList::len(self)
+    }
+
+    fn clear(&mut self) {
+        collections::LinkedList::clear(self);
+    }
+}
+
+impl<T> Deque<T> for collections::LinkedList<T>
+where
+    T: Clone,
+{
+    fn push_front(&mut self, value: T) {
+        collections::LinkedList::push_front(self, value);
+    }
+
+    fn push_back(&mut self, value: T) {
+        collections::LinkedList::push_back(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        collections::LinkedList::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        collections::LinkedList::pop_back(self)
+    }
+
+    fn front(&self) -> Option<T> {
+        collections::LinkedList::front(self).cloned()
+    }
+
+    fn back(&self) -> Option<T> {
+        collections::LinkedList::back(self).cloned()
+    }
+}
+
+impl<K, V> Collection for BTreeMap<K, V> {
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        BTreeMap::clear(self);
+    }
+}
+
+impl<K, V> OrderedMap<K, V> for BTreeMap<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        BTreeMap::get(self, key).cloned()
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        BTreeMap::remove(self, key)
+    }
+
+    fn range<R>(&self, range: R) -> impl Iterator<Item = (K, V)>
+    where
+        R: RangeBounds<K>,
+    {
+        BTreeMap::range(self, range).map(|(key, value)| (key.clone(), value.clone()))
+    }
+
+    fn first(&self) -> Option<(K, V)> {
+        self.first_key_value()
+            .map(|(key, value)| (key.clone(), value.clone()))
+    }
+
+    fn last(&self) -> Option<(K, V)> {
+        self.last_key_value()
+            .map(|(key, value)| (key.clone(), value.clone()))
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        BTreeMap::contains_key(self, key)
+    }
+}
+
+#[cfg(all(test, feature = "linked-list", feature = "avl"))]
+mod traits_tests {
+    use std::collections::{self, BTreeMap, VecDeque};
+
+    use super::{Deque, OrderedMap};
+    use crate::{avl_tree::AVLTree, linked_list::LinkedList, unrolled_list::UnrolledList};
+
+    fn exercise_deque<D: Deque<u32> + Default>() -> Vec<Option<u32>> {
+        let mut deque = D::default();
+        let mut seen = vec![deque.front(), deque.pop_back()];
+
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        seen.push(deque.front());
+        seen.push(deque.back());
+        seen.push(deque.pop_back());
+        seen.push(deque.pop_front());
+        seen.push(Some(deque.len() as u32));
+
+        deque.clear();
+        assert!(deque.is_empty());
+        seen
+    }
+
+    fn exercise_map<M: OrderedMap<u32, char> + Default>() -> Vec<Option<(u32, char)>> {
+        let mut map = M::default();
+        let mut seen = vec![map.first(), map.last()];
+
+        for (key, value) in [(5, 'e'), (1, 'a'), (3, 'c'), (9, 'i'), (7, 'g')] {
+            assert_eq!(map.insert(key, value), None);
+        }
+        assert_eq!(map.insert(3, 'C'), Some('c'));
+        assert_eq!(map.get(&3), Some('C'));
+        assert_eq!(map.get(&4), None);
+        assert!(map.contains_key(&9));
+        assert_eq!(map.remove(&9), Some('i'));
+        assert_eq!(map.remove(&9), None);
+        assert_eq!(map.len(), 4);
+
+        seen.push(map.first());
+        seen.push(map.last());
+        seen.extend(map.range(2..7).map(Some));
+
+        map.clear();
+        assert!(map.is_empty());
+        seen
+    }
+
+    #[test]
+    fn test_deques_agree() {
+        let expected = exercise_deque::<VecDeque<u32>>();
+
+        assert_eq!(expected[2..], [Some(1), Some(3), Some(3), Some(1), Some(1)]);
+        assert_eq!(exercise_deque::<LinkedList<u32>>(), expected);
+        assert_eq!(exercise_deque::<collections::LinkedList<u32>>(), expected);
+        assert_eq!(exercise_deque::<UnrolledList<u32>>(), expected);
+        assert_eq!(exercise_deque::<UnrolledList<u32, 2>>(), expected);
+    }
+
+    #[test]
+    fn test_ordered_maps_agree() {
+        let expected = exercise_map::<BTreeMap<u32, char>>();
+
+        assert_eq!(
+            expected[2..],
+            [
+                Some((1, 'a')),
+                Some((7, 'g')),
+                Some((3, 'C')),
+                Some((5, 'e'))
+            ]
+        );
+        assert_eq!(exercise_map::<AVLTree<u32, char>>(), expected);
+    }
+}
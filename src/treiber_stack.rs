@@ -0,0 +1,188 @@
+use std::{
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::hazard::Domain;
+
+// `value` is moved out by whichever `pop` unlinks the node, so freeing a
+// retired node must not drop it again.
+struct Node<T> {
+    value: ManuallyDrop<T>,
+    next: *mut Node<T>,
+}
+
+// A lock-free LIFO. Pushes and pops race on a single CAS of `head`; popped
+// nodes go through hazard pointers, so a node is never freed while another
+// thread may still read its `next`, which also rules out ABA.
+pub struct TreiberStack<T> {
+    head: AtomicPtr<Node<T>>,
+    domain: Domain,
+}
+
+unsafe impl<T: Send> Send for TreiberStack<T> {}
+unsafe impl<T: Send> Sync for TreiberStack<T> {}
+
+impl<T> TreiberStack<T> {
+    pub fn new() -> Self {
+        TreiberStack {
+            head: AtomicPtr::new(ptr::null_mut()),
+            domain: Domain::new(),
+        }
+    }
+
+    // Only a snapshot while other threads are pushing or popping.
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    pub fn push(&self, value: T) {
+        let node = Box::into_raw(Box::new(Node {
+            value: ManuallyDrop::new(value),
+            next: ptr::null_mut(),
+        }));
+
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*node).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.domain.guard();
+        loop {
+            let head = guard.protect(&self.head);
+            if head.is_null() {
+                return None;
+            }
+
+            // `next` is never written once the node is published.
+            let next = unsafe { (*head).next };
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                let value = unsafe { ManuallyDrop::take(&mut (*head).value) };
+                drop(guard);
+                unsafe { self.domain.retire(head) };
+                return Some(value);
+            }
+        }
+    }
+}
+
+impl<T> Default for TreiberStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        let mut node = *self.head.get_mut();
+        while !node.is_null() {
+            let mut current = unsafe { Box::from_raw(node) };
+            unsafe { ManuallyDrop::drop(&mut current.value) };
+            node = current.next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod treiber_stack_tests {
+    use std::{
+        collections::HashSet,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    use super::TreiberStack;
+
+    #[test]
+    fn test_push_and_pop() {
+        let stack = TreiberStack::new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+
+        for value in 0..5 {
+            stack.push(value);
+        }
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(3));
+        stack.push(9);
+        assert_eq!(stack.pop(), Some(9));
+        assert!(!stack.is_empty());
+    }
+
+    #[test]
+    fn test_drops_every_value_once() {
+        struct Counted(Arc<AtomicUsize>);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let stack = TreiberStack::new();
+        for _ in 0..1_000 {
+            stack.push(Counted(dropped.clone()));
+        }
+        for _ in 0..600 {
+            drop(stack.pop());
+        }
+        assert_eq!(dropped.load(Ordering::Relaxed), 600);
+
+        drop(stack);
+        assert_eq!(dropped.load(Ordering::Relaxed), 1_000);
+    }
+
+    #[test]
+    fn test_concurrent_push_and_pop() {
+        const THREADS: usize = 8;
+        const ITEMS: usize = 20_000;
+
+        let stack = Arc::new(TreiberStack::new());
+        let workers: Vec<_> = (0..THREADS)
+            .map(|thread| {
+                let stack = stack.clone();
+                thread::spawn(move || {
+                    let mut popped = Vec::new();
+                    for item in 0..ITEMS {
+                        stack.push(thread * ITEMS + item);
+                        if item % 2 == 1 {
+                            popped.extend(stack.pop());
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for worker in workers {
+            for value in worker.join().unwrap() {
+                assert!(seen.insert(value), "{value} was popped twice");
+            }
+        }
+        while let Some(value) = stack.pop() {
+            assert!(seen.insert(value), "{value} was popped twice");
+        }
+
+        // Nothing lost and nothing duplicated.
+        assert_eq!(seen.len(), THREADS * ITEMS);
+    }
+}
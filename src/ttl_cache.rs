@@ -0,0 +1,235 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    avl_tree::AVLTree,
+    clock::{Clock, SystemClock},
+};
+
+// Entries expiring at the same instant are told apart by insertion order.
+type Expiry = (Deadline, u64);
+
+// A TTL too long for `Instant` to represent, such as `Duration::MAX`, never
+// expires. `Never` sorts after every instant, so sweeps never reach it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Deadline {
+    At(Instant),
+    Never,
+}
+
+impl Deadline {
+    fn after(now: Instant, ttl: Duration) -> Self {
+        now.checked_add(ttl).map_or(Deadline::Never, Deadline::At)
+    }
+
+    fn is_live(self, now: Instant) -> bool {
+        match self {
+            Deadline::At(expires) => expires > now,
+            Deadline::Never => true,
+        }
+    }
+}
+
+// Every entry sits in two trees: one by key for lookups, one by expiry so a
+// sweep is a single range scan over everything that is already due.
+pub struct TtlCache<K, V, C = SystemClock>
+where
+    K: Ord + Clone,
+    C: Clock,
+{
+    entries: AVLTree<K, (V, Expiry)>,
+    expiries: AVLTree<Expiry, K>,
+    ttl: Duration,
+    clock: C,
+    sequence: u64,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Ord + Clone,
+{
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_clock(ttl, SystemClock)
+    }
+}
+
+impl<K, V, C> TtlCache<K, V, C>
+where
+    K: Ord + Clone,
+    C: Clock,
+{
+    pub fn with_clock(ttl: Duration, clock: C) -> Self {
+        TtlCache {
+            entries: AVLTree::new(),
+            expiries: AVLTree::new(),
+            ttl,
+            clock,
+            sequence: 0,
+        }
+    }
+
+    // Counts expired entries until they are swept.
+    pub fn len(&self) -> usize {
+        self.entries.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_with_ttl(key, value, self.ttl)
+    }
+
+    // Sweeps first, so the value returned is only ever a live one.
+    pub fn insert_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<V> {
+        self.sweep();
+
+        let old = self.take(&key).map(|(value, _)| value);
+        let expiry = (Deadline::after(self.clock.now(), ttl), self.sequence);
+        self.sequence += 1;
+
+        let _ = self.expiries.insert(expiry, key.clone());
+        let _ = self.entries.insert(key, (value, expiry));
+
+        old
+    }
+
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        let (value, (expires, _)) = self.entries.get(key.clone()).ok()?;
+        expires.is_live(self.clock.now()).then_some(value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool
+    where
+        V: Clone,
+    {
+        self.time_to_live(key).is_some()
+    }
+
+    // How long the entry has left, or `None` once it has expired. An entry
+    // that never expires has `Duration::MAX` left.
+    pub fn time_to_live(&self, key: &K) -> Option<Duration>
+    where
+        V: Clone,
+    {
+        let (_, (expires, _)) = self.entries.get(key.clone()).ok()?;
+        let now = self.clock.now();
+        match expires {
+            Deadline::At(expires) => (expires > now).then(|| expires - now),
+            Deadline::Never => Some(Duration::MAX),
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, (expires, _)) = self.take(key)?;
+        expires.is_live(self.clock.now()).then_some(value)
+    }
+
+    // Drops every expired entry and hands them back, oldest expiry first.
+    pub fn sweep(&mut self) -> Vec<(K, V)> {
+        let due: Vec<(Expiry, K)> = self
+            .expiries
+            .range(..=(Deadline::At(self.clock.now()), u64::MAX))
+            .collect();
+
+        due.into_iter()
+            .filter_map(|(expiry, key)| {
+                let _ = self.expiries.remove(expiry);
+                let (value, _) = self.entries.remove(key.clone()).ok()?;
+                Some((key, value))
+            })
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.expiries.clear();
+    }
+
+    fn take(&mut self, key: &K) -> Option<(V, Expiry)> {
+        let (value, expiry) = self.entries.remove(key.clone()).ok()?;
+        let _ = self.expiries.remove(expiry);
+        Some((value, expiry))
+    }
+}
+
+#[cfg(test)]
+mod ttl_cache_tests {
+    use std::time::Duration;
+
+    use super::TtlCache;
+    use crate::clock::ManualClock;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn test_expiry() {
+        let clock = ManualClock::new();
+        let mut cache = TtlCache::with_clock(SECOND * 10, clock.clone());
+
+        cache.insert("a", 1);
+        clock.advance(SECOND * 4);
+        cache.insert("b", 2);
+        assert_eq!(cache.time_to_live(&"a"), Some(SECOND * 6));
+
+        clock.advance(SECOND * 6);
+        assert_eq!(cache.get(&"a"), None);
+        assert!(!cache.contains_key(&"a"));
+        assert_eq!(cache.get(&"b"), Some(2));
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.sweep(), [("a", 1)]);
+        assert_eq!(cache.len(), 1);
+
+        clock.advance(SECOND * 4);
+        assert_eq!(cache.remove(&"b"), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_overwrite_and_custom_ttl() {
+        let clock = ManualClock::new();
+        let mut cache = TtlCache::with_clock(SECOND * 5, clock.clone());
+
+        assert_eq!(cache.insert(1, 'x'), None);
+        assert_eq!(cache.insert_with_ttl(2, 'y', SECOND), None);
+        assert_eq!(cache.insert_with_ttl(3, 'z', SECOND), None);
+
+        // Overwriting restarts the clock for that entry.
+        clock.advance(SECOND * 3);
+        assert_eq!(cache.insert(1, 'X'), Some('x'));
+        assert_eq!(cache.len(), 1);
+
+        clock.advance(SECOND * 3);
+        assert_eq!(cache.get(&1), Some('X'));
+        assert_eq!(cache.insert(1, 'Y'), Some('X'));
+        assert_eq!(cache.remove(&1), Some('Y'));
+
+        cache.insert(4, 'w');
+        cache.clear();
+        assert!(cache.sweep().is_empty());
+    }
+
+    #[test]
+    fn test_unbounded_ttl() {
+        let clock = ManualClock::new();
+        let mut cache = TtlCache::with_clock(Duration::MAX, clock.clone());
+
+        cache.insert("forever", 1);
+        cache.insert_with_ttl("brief", 2, SECOND);
+        clock.advance(SECOND * 1_000_000_000);
+
+        assert_eq!(cache.sweep(), [("brief", 2)]);
+        assert_eq!(cache.get(&"forever"), Some(1));
+        assert_eq!(cache.time_to_live(&"forever"), Some(Duration::MAX));
+        assert_eq!(cache.insert("forever", 3), Some(1));
+        assert_eq!(cache.remove(&"forever"), Some(3));
+    }
+}
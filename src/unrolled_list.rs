@@ -0,0 +1,644 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    iter::FromIterator,
+    marker::PhantomData,
+    rc::{Rc, Weak},
+};
+
+use crate::linked_list::LinkedListError;
+
+type ChunkRef<T, const B: usize> = Rc<RefCell<Chunk<T, B>>>;
+type Link<T, const B: usize> = Option<ChunkRef<T, B>>;
+
+// Up to `B` values stored inline, so walking a chunk touches one or two cache
+// lines instead of one heap node per value. Slots at `len` and beyond hold
+// stale copies, which is why a chunk is only ever created around a value.
+struct Chunk<T, const B: usize>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    items: [T; B],
+    len: usize,
+    prev: Option<Weak<RefCell<Chunk<T, B>>>>,
+    next: Link<T, B>,
+}
+
+impl<T, const B: usize> Chunk<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn new(item: T) -> ChunkRef<T, B> {
+        Rc::new(RefCell::new(Chunk {
+            items: [item; B],
+            len: 1,
+            prev: None,
+            next: None,
+        }))
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == B
+    }
+
+    fn insert(&mut self, offset: usize, item: T) {
+        self.items.copy_within(offset..self.len, offset + 1);
+        self.items[offset] = item;
+        self.len += 1;
+    }
+
+    fn remove(&mut self, offset: usize) -> T {
+        let item = self.items[offset];
+        self.items.copy_within(offset + 1..self.len, offset);
+        self.len -= 1;
+        item
+    }
+}
+
+// A doubly linked list of chunks. Interior chunks are kept at least half
+// full, so apart from the two ends the list never uses much more than twice
+// the memory of its values, and a lookup walks about `2 * length / B` chunks
+// at most. The end chunks can hold as little as one value: pushing onto a
+// full end starts a new chunk, and pops shrink the ends without merging.
+pub struct UnrolledList<T, const B: usize = 32>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    head: Link<T, B>,
+    tail: Link<T, B>,
+    length: usize,
+}
+
+impl<T, const B: usize> UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    // Panics if `B` is below 2, since a chunk could then never be split.
+    pub fn new() -> Self {
+        assert!(B >= 2, "an unrolled list needs at least 2 values per chunk");
+
+        UnrolledList {
+            head: None,
+            tail: None,
+            length: 0,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.tail = None;
+        self.length = 0;
+
+        let mut chunk = self.head.take();
+        while let Some(temp) = chunk {
+            chunk = temp.borrow_mut().next.take();
+        }
+    }
+
+    pub fn contains(&self, item: T) -> bool {
+        self.iter().any(|value| value == item)
+    }
+
+    pub fn front(&self) -> Result<T, LinkedListError> {
+        let head = self.head.as_ref().ok_or(LinkedListError::EmptyList)?;
+        let item = head.borrow().items[0];
+        Ok(item)
+    }
+
+    pub fn back(&self) -> Result<T, LinkedListError> {
+        let tail = self.tail.as_ref().ok_or(LinkedListError::EmptyList)?;
+        let tail = tail.borrow();
+        Ok(tail.items[tail.len - 1])
+    }
+
+    pub fn get(&self, idx: usize) -> Result<T, LinkedListError> {
+        let (chunk, offset) = self.locate(idx)?;
+        let item = chunk.borrow().items[offset];
+        Ok(item)
+    }
+
+    pub fn push_front(&mut self, item: T) {
+        match &self.head {
+            Some(head) if !head.borrow().is_full() => head.borrow_mut().insert(0, item),
+            _ => {
+                let chunk = Chunk::new(item);
+                match self.head.take() {
+                    Some(head) => {
+                        head.borrow_mut().prev = Some(Rc::downgrade(&chunk));
+                        chunk.borrow_mut().next = Some(head);
+                    }
+                    None => self.tail = Some(chunk.clone()),
+                }
+                self.head = Some(chunk);
+            }
+        }
+        self.length += 1;
+    }
+
+    pub fn push_back(&mut self, item: T) {
+        match self.tail.clone() {
+            Some(tail) if !tail.borrow().is_full() => {
+                let mut tail = tail.borrow_mut();
+                let len = tail.len;
+                tail.insert(len, item);
+            }
+            Some(tail) => self.link_after(&tail, Chunk::new(item)),
+            None => {
+                let chunk = Chunk::new(item);
+                self.head = Some(chunk.clone());
+                self.tail = Some(chunk);
+            }
+        }
+        self.length += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Result<T, LinkedListError> {
+        let head = self.head.clone().ok_or(LinkedListError::EmptyList)?;
+        let item = head.borrow_mut().remove(0);
+        self.length -= 1;
+
+        if head.borrow().len == 0 {
+            self.unlink(&head);
+        }
+        Ok(item)
+    }
+
+    pub fn pop_back(&mut self) -> Result<T, LinkedListError> {
+        let tail = self.tail.clone().ok_or(LinkedListError::EmptyList)?;
+        let item = {
+            let mut tail = tail.borrow_mut();
+            let len = tail.len;
+            tail.remove(len - 1)
+        };
+        self.length -= 1;
+
+        if tail.borrow().len == 0 {
+            self.unlink(&tail);
+        }
+        Ok(item)
+    }
+
+    // A full chunk is split in half first, so an insert shifts at most `B`
+    // values and never cascades into the neighbours.
+    pub fn insert(&mut self, idx: usize, item: T) -> Result<(), LinkedListError> {
+        if idx == self.length {
+            self.push_back(item);
+            return Ok(());
+        }
+
+        let (chunk, offset) = self.locate(idx)?;
+        if !chunk.borrow().is_full() {
+            chunk.borrow_mut().insert(offset, item);
+            self.length += 1;
+            return Ok(());
+        }
+
+        let half = B / 2;
+        let upper = {
+            let mut chunk = chunk.borrow_mut();
+            let upper = Chunk::new(chunk.items[half]);
+            {
+                let mut upper = upper.borrow_mut();
+                upper.len = B - half;
+                upper.items[..B - half].copy_from_slice(&chunk.items[half..]);
+            }
+            chunk.len = half;
+            upper
+        };
+        self.link_after(&chunk, upper.clone());
+
+        if offset <= half {
+            chunk.borrow_mut().insert(offset, item);
+        } else {
+            upper.borrow_mut().insert(offset - half, item);
+        }
+        self.length += 1;
+        Ok(())
+    }
+
+    // A chunk that drops below half full takes values from the next one, or
+    // merges with it when both fit in a single chunk.
+    pub fn remove(&mut self, idx: usize) -> Result<T, LinkedListError> {
+        let (chunk, offset) = self.locate(idx)?;
+        let item = chunk.borrow_mut().remove(offset);
+        self.length -= 1;
+
+        if chunk.borrow().len == 0 {
+            self.unlink(&chunk);
+            return Ok(item);
+        }
+        if chunk.borrow().len >= B / 2 {
+            return Ok(item);
+        }
+
+        let Some(next) = chunk.borrow().next.clone() else {
+            return Ok(item);
+        };
+        let merged = {
+            let mut chunk = chunk.borrow_mut();
+            let mut next = next.borrow_mut();
+            let (len, next_len) = (chunk.len, next.len);
+
+            if len + next_len <= B {
+                chunk.items[len..len + next_len].copy_from_slice(&next.items[..next_len]);
+                chunk.len += next_len;
+                true
+            } else {
+                let moved = (next_len - len) / 2;
+                chunk.items[len..len + moved].copy_from_slice(&next.items[..moved]);
+                chunk.len += moved;
+                next.items.copy_within(moved..next_len, 0);
+                next.len -= moved;
+                false
+            }
+        };
+        if merged {
+            self.unlink(&next);
+        }
+
+        Ok(item)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, B> {
+        Iter {
+            chunk: self.head.clone(),
+            offset: 0,
+            remaining: self.length,
+            marker: PhantomData,
+        }
+    }
+
+    // The chunk holding index `idx` and the offset within it, walking from
+    // whichever end is closer.
+    fn locate(&self, idx: usize) -> Result<(ChunkRef<T, B>, usize), LinkedListError> {
+        if idx >= self.length {
+            return Err(LinkedListError::OutOfBounds {
+                index: idx,
+                len: self.length,
+            });
+        }
+
+        if idx < self.length / 2 {
+            let mut chunk = self.head.clone();
+            let mut start = 0;
+            while let Some(current) = chunk {
+                let (len, next) = {
+                    let current = current.borrow();
+                    (current.len, current.next.clone())
+                };
+                if idx < start + len {
+                    return Ok((current, idx - start));
+                }
+                start += len;
+                chunk = next;
+            }
+        } else {
+            let mut chunk = self.tail.clone();
+            let mut end = self.length;
+            while let Some(current) = chunk {
+                let (len, prev) = {
+                    let current = current.borrow();
+                    (current.len, current.prev.as_ref().and_then(Weak::upgrade))
+                };
+                if idx >= end - len {
+                    return Ok((current, idx - (end - len)));
+                }
+                end -= len;
+                chunk = prev;
+            }
+        }
+
+        unreachable!("the chunk lengths add up to the list length")
+    }
+
+    fn link_after(&mut self, chunk: &ChunkRef<T, B>, new: ChunkRef<T, B>) {
+        let next = chunk.borrow_mut().next.take();
+        {
+            let mut linked = new.borrow_mut();
+            linked.prev = Some(Rc::downgrade(chunk));
+            linked.next = next.clone();
+        }
+        match next {
+            Some(next) => next.borrow_mut().prev = Some(Rc::downgrade(&new)),
+            None => self.tail = Some(new.clone()),
+        }
+        chunk.borrow_mut().next = Some(new);
+    }
+
+    fn unlink(&mut self, chunk: &ChunkRef<T, B>) {
+        let (prev, next) = {
+            let mut chunk = chunk.borrow_mut();
+            let prev = chunk.prev.take().and_then(|prev| prev.upgrade());
+            (prev, chunk.next.take())
+        };
+
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev,
+        }
+    }
+}
+
+impl<T, const B: usize> Drop for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+pub struct Iter<'a, T, const B: usize>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    chunk: Link<T, B>,
+    offset: usize,
+    remaining: usize,
+    marker: PhantomData<&'a UnrolledList<T, B>>,
+}
+
+impl<T, const B: usize> Iterator for Iter<'_, T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let next = {
+                let chunk = self.chunk.as_ref()?.borrow();
+                if self.offset < chunk.len {
+                    let item = chunk.items[self.offset];
+                    self.offset += 1;
+                    self.remaining -= 1;
+                    return Some(item);
+                }
+                chunk.next.clone()
+            };
+            self.chunk = next;
+            self.offset = 0;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, const B: usize> ExactSizeIterator for Iter<'_, T, B> where T: Copy + std::cmp::PartialEq {}
+
+pub struct IntoIter<T, const B: usize>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    list: UnrolledList<T, B>,
+}
+
+impl<T, const B: usize> Iterator for IntoIter<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.length, Some(self.list.length))
+    }
+}
+
+impl<T, const B: usize> ExactSizeIterator for IntoIter<T, B> where T: Copy + std::cmp::PartialEq {}
+
+impl<T, const B: usize> IntoIterator for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, B>;
+
+    fn into_iter(self) -> IntoIter<T, B> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T, const B: usize> IntoIterator for &'a UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T, B>;
+
+    fn into_iter(self) -> Iter<'a, T, B> {
+        self.iter()
+    }
+}
+
+impl<T, const B: usize> FromIterator<T> for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = UnrolledList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, const B: usize> Extend<T> for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T, const B: usize> From<Vec<T>> for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn from(value: Vec<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<T, const B: usize> Default for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn default() -> Self {
+        UnrolledList::new()
+    }
+}
+
+impl<T, const B: usize> Clone for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn clone(&self) -> Self {
+        self.iter().collect()
+    }
+}
+
+impl<T, const B: usize> fmt::Debug for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const B: usize> PartialEq for UnrolledList<T, B>
+where
+    T: Copy + std::cmp::PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, const B: usize> Eq for UnrolledList<T, B> where T: Copy + Eq {}
+
+#[cfg(test)]
+mod unrolled_list_tests {
+    use std::collections::VecDeque;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::UnrolledList;
+    use crate::linked_list::LinkedListError;
+
+    // Chunk lengths add up, back links match, and no chunk between the two
+    // ends is less than half full.
+    fn check<const B: usize>(list: &UnrolledList<u32, B>) -> Vec<usize> {
+        let mut lengths = Vec::new();
+        let mut chunk = list.head.clone();
+        let mut prev = None;
+        while let Some(current) = chunk {
+            let current_ref = current.borrow();
+            let back = current_ref.prev.as_ref().and_then(|prev| prev.upgrade());
+            assert!(back.as_ref().map(|back| back.as_ptr()) == prev);
+            assert!(current_ref.len > 0 && current_ref.len <= B);
+            lengths.push(current_ref.len);
+            prev = Some(current.as_ptr());
+            chunk = current_ref.next.clone();
+        }
+        assert_eq!(list.tail.as_ref().map(|tail| tail.as_ptr()), prev);
+        assert_eq!(lengths.iter().sum::<usize>(), list.length());
+        if let [_, interior @ .., _] = lengths.as_slice() {
+            assert!(interior.iter().all(|&len| len >= B / 2), "{lengths:?}");
+        }
+        lengths
+    }
+
+    #[test]
+    fn test_split_and_merge() {
+        let mut list: UnrolledList<u32, 4> = (0..8).collect();
+        assert_eq!(check(&list), [4, 4]);
+
+        // A full chunk splits, and the new value lands in the lower half.
+        list.insert(1, 100).unwrap();
+        assert_eq!(check(&list), [3, 2, 4]);
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            [0, 100, 1, 2, 3, 4, 5, 6, 7]
+        );
+
+        assert_eq!(list.remove(0), Ok(0));
+        assert_eq!(check(&list), [2, 2, 4]);
+
+        // Too many to merge, so it takes one value from the next chunk.
+        assert_eq!(list.remove(2), Ok(2));
+        assert_eq!(check(&list), [2, 2, 3]);
+
+        assert_eq!(list.remove(0), Ok(100));
+        assert_eq!(check(&list), [3, 3]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), [1, 3, 4, 5, 6, 7]);
+
+        assert_eq!(list.get(3), Ok(5));
+        assert_eq!(
+            list.get(6),
+            Err(LinkedListError::OutOfBounds { index: 6, len: 6 })
+        );
+        assert_eq!(
+            list.insert(7, 0),
+            Err(LinkedListError::OutOfBounds { index: 7, len: 6 })
+        );
+    }
+
+    #[test]
+    fn test_against_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut list = UnrolledList::<u32, 8>::new();
+        let mut model = VecDeque::new();
+
+        for step in 0..20_000 {
+            let value = rng.gen();
+            match rng.gen_range(0..8) {
+                0 => {
+                    list.push_front(value);
+                    model.push_front(value);
+                }
+                1 => {
+                    list.push_back(value);
+                    model.push_back(value);
+                }
+                2 => assert_eq!(list.pop_front().ok(), model.pop_front()),
+                3 => assert_eq!(list.pop_back().ok(), model.pop_back()),
+                4 | 5 => {
+                    let idx = rng.gen_range(0..=model.len());
+                    list.insert(idx, value).unwrap();
+                    model.insert(idx, value);
+                }
+                _ if model.is_empty() => assert!(list.remove(0).is_err()),
+                _ => {
+                    let idx = rng.gen_range(0..model.len());
+                    assert_eq!(list.remove(idx).ok(), model.remove(idx));
+                }
+            }
+
+            if step % 500 == 0 {
+                check(&list);
+                assert!(list.iter().eq(model.iter().copied()));
+            }
+        }
+
+        assert_eq!(list.front().ok(), model.front().copied());
+        assert_eq!(list.back().ok(), model.back().copied());
+        for (idx, &value) in model.iter().enumerate() {
+            assert_eq!(list.get(idx), Ok(value));
+        }
+        assert!(list.clone().into_iter().eq(model.into_iter()));
+    }
+
+    #[test]
+    fn test_empty_and_drop() {
+        let mut list = UnrolledList::<u32>::new();
+        assert_eq!(list.pop_front(), Err(LinkedListError::EmptyList));
+        assert_eq!(list.back(), Err(LinkedListError::EmptyList));
+
+        list.extend(0..1_000_000);
+        assert!(list.contains(999_999));
+        assert_eq!(
+            format!("{:?}", UnrolledList::<u32, 2>::from(vec![1, 2, 3])),
+            "[1, 2, 3]"
+        );
+    }
+}